@@ -4,13 +4,350 @@ use ink_lang as ink;
 
 #[ink::contract]
 mod erc20 {
-    use ink_storage::collections::HashMap as StorageHashMap;
+    use core::convert::TryFrom;
+    use ink_env::hash::Blake2x256;
+    #[cfg(not(test))]
+    use ink_env::{
+        call::{build_call, Call, ExecutionInput, Selector},
+        CallFlags,
+    };
+    use ink_prelude::{string::String, vec::Vec};
+    use ink_primitives::Key;
+    use ink_storage::{
+        traits::{
+            pull_spread_root, push_spread_root, KeyPtr, PackedLayout, SpreadAllocate, SpreadLayout,
+        },
+        Mapping,
+    };
+
+    /// Where ink! keeps the contract's own fields.
+    const ROOT_KEY: Key = Key::new([0x00; 32]);
+
+    /// Longest memo accepted by `transfer_with_memo`, in bytes.
+    pub const MAX_MEMO_LEN: usize = 64;
+
+    /// Most accounts accepted by `balances_of` in a single call.
+    pub const MAX_BATCH_LEN: usize = 256;
+
+    /// Largest `decimals` a token can be created with. One whole token is
+    /// then `10^MAX_DECIMALS` base units, which still fits in a `Balance`.
+    pub const MAX_DECIMALS: u8 = 36;
+
+    /// Most holders returned by `top_holders`.
+    pub const MAX_TOP_HOLDERS: u32 = 100;
+
+    /// Basis points in 100%.
+    pub const BASIS_POINTS: u16 = 10_000;
+
+    /// Upper bound for `fee_bps`.
+    pub const MAX_FEE_BPS: u16 = BASIS_POINTS;
+
+    /// Transfers kept by `recent_transfers` unless a deployment picks its own
+    /// limit with `new_with_recent_transfers_cap`.
+    pub const DEFAULT_RECENT_TRANSFERS_CAP: u32 = 32;
+
+    /// Bytes of the default dead address: where `burn` sends tokens when
+    /// `burn_to_address` is set, and where transfers are burned when
+    /// `auto_burn` is. Nobody holds a key for it.
+    pub const DEAD: [u8; 32] = [0xde; 32];
+
+    /// Supply changes kept by `supply_log` unless a deployment picks its own
+    /// limit with `new_with_supply_log_cap`.
+    pub const DEFAULT_SUPPLY_LOG_CAP: u32 = 32;
+
+    /// What a contract's `on_erc20_received` must return to accept a
+    /// `safe_transfer`: the selector of `on_erc20_received` itself.
+    pub const ERC20_RECEIVED: [u8; 4] = ink_lang::selector_bytes!("on_erc20_received");
+
+    /// Custom logic run around every `transfer`. Both methods default to
+    /// no-ops, so an implementation only overrides the side it needs.
+    #[cfg(feature = "hooks")]
+    pub trait TransferHook {
+        fn before_transfer(&self, _from: AccountId, _to: AccountId, _value: Balance) {}
+
+        fn after_transfer(&self, _from: AccountId, _to: AccountId, _value: Balance) {}
+    }
+
+    /// The hook used unless a build swaps in its own.
+    #[cfg(all(feature = "hooks", not(test)))]
+    #[derive(Default)]
+    pub struct NoopHook;
+
+    #[cfg(all(feature = "hooks", not(test)))]
+    impl TransferHook for NoopHook {}
+
+    /// The hook `transfer` calls when the `hooks` feature is enabled. Point
+    /// this at another `TransferHook + Default` to run custom logic.
+    #[cfg(all(feature = "hooks", not(test)))]
+    pub type ActiveHook = NoopHook;
+
+    #[cfg(all(feature = "hooks", test))]
+    pub type ActiveHook = tests::CountingHook;
+
+    /// The calls the token makes to other contracts. The off-chain test
+    /// environment can neither tell contracts from plain accounts nor call
+    /// them, so tests answer these with `tests::MockCallee` instead.
+    trait Callee {
+        fn is_contract(&self, account: AccountId) -> bool;
+
+        /// `on_token_transfer(from, value, data)` on `to`. False if the call
+        /// failed.
+        fn on_token_transfer(
+            &self,
+            to: AccountId,
+            from: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> bool;
+
+        /// `receive_approval(owner, value, data)` on `spender`, which may
+        /// re-enter the token. False if the call failed.
+        fn receive_approval(
+            &self,
+            spender: AccountId,
+            owner: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> bool;
+
+        /// `on_erc20_received(from, value)` on `to`, or `None` if the call
+        /// failed.
+        fn on_erc20_received(
+            &self,
+            to: AccountId,
+            from: AccountId,
+            value: Balance,
+        ) -> Option<[u8; 4]>;
+    }
+
+    /// Calls other contracts through the contracts pallet.
+    #[cfg(not(test))]
+    #[derive(Default)]
+    struct EnvCallee;
+
+    #[cfg(not(test))]
+    impl Callee for EnvCallee {
+        fn is_contract(&self, account: AccountId) -> bool {
+            ink_env::is_contract::<ink_env::DefaultEnvironment>(&account)
+        }
+
+        fn on_token_transfer(
+            &self,
+            to: AccountId,
+            from: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> bool {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(to))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink_lang::selector_bytes!(
+                        "on_token_transfer"
+                    )))
+                    .push_arg(from)
+                    .push_arg(value)
+                    .push_arg(data),
+                )
+                .returns::<()>()
+                .fire()
+                .is_ok()
+        }
+
+        fn receive_approval(
+            &self,
+            spender: AccountId,
+            owner: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> bool {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(spender))
+                .call_flags(CallFlags::default().set_allow_reentry(true))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink_lang::selector_bytes!(
+                        "receive_approval"
+                    )))
+                    .push_arg(owner)
+                    .push_arg(value)
+                    .push_arg(data),
+                )
+                .returns::<()>()
+                .fire()
+                .is_ok()
+        }
+
+        fn on_erc20_received(
+            &self,
+            to: AccountId,
+            from: AccountId,
+            value: Balance,
+        ) -> Option<[u8; 4]> {
+            build_call::<ink_env::DefaultEnvironment>()
+                .call_type(Call::new().callee(to))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink_lang::selector_bytes!(
+                        "on_erc20_received"
+                    )))
+                    .push_arg(from)
+                    .push_arg(value),
+                )
+                .returns::<[u8; 4]>()
+                .fire()
+                .ok()
+        }
+    }
+
+    #[cfg(not(test))]
+    type ActiveCallee = EnvCallee;
+
+    #[cfg(test)]
+    type ActiveCallee = tests::MockCallee;
+
     #[ink(storage)]
+    #[derive(SpreadAllocate)]
     pub struct Erc20 {
         issuer: AccountId,
+        name: Option<String>,
+        symbol: Option<String>,
+        decimals: u8,
         total_supply: Balance,
-        balances: StorageHashMap<AccountId, Balance>,
-        allowances: StorageHashMap<(AccountId, AccountId), Balance>,
+        cap: Option<Balance>,
+        paused: bool,
+        balances: Mapping<AccountId, Balance>,
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        frozen: Mapping<AccountId, bool>,
+        holder_count: u32,
+        nonces: Mapping<AccountId, u64>,
+        snapshot_id: u32,
+        balance_snapshots: Mapping<AccountId, Vec<(u32, Balance)>>,
+        /// The total supply at each snapshot id, written lazily like balance
+        /// checkpoints. Kept out of the root struct so it does not weigh on
+        /// every message.
+        total_supply_snapshots: Mapping<u32, Balance>,
+        /// The latest snapshot id with an entry in `total_supply_snapshots`.
+        total_supply_checkpoint: u32,
+        /// Every account that has ever been credited, in first-credit order.
+        /// `Mapping` cannot be iterated, so this index is what keeps balances
+        /// enumerable.
+        accounts: Mapping<u32, AccountId>,
+        account_count: u32,
+        seen: Mapping<AccountId, bool>,
+        /// Multi-signature issuers. Empty means `issuer` acts alone.
+        issuers: Vec<AccountId>,
+        threshold: u32,
+        proposals: Mapping<u32, Proposal>,
+        proposal_count: u32,
+        /// Blocks between an issue and the moment it can be executed.
+        mint_delay: BlockNumber,
+        /// Queued issues as `(issuer, to, value, ready_at)`.
+        pending_issues: Mapping<u32, (AccountId, AccountId, Balance, BlockNumber)>,
+        pending_issue_count: u32,
+        /// Holds issuer reserves that do not count as circulating.
+        treasury: AccountId,
+        /// Beneficiary to `(total, start_block, duration)`. The escrowed tokens
+        /// sit in the contract's own account until claimed.
+        vesting: Mapping<AccountId, (Balance, BlockNumber, BlockNumber)>,
+        vesting_claimed: Mapping<AccountId, Balance>,
+        /// When above one, transferred amounts must be a multiple of it.
+        unit_size: Balance,
+        /// Requires an allowance to go through zero before it is changed.
+        strict_approve: bool,
+        /// Accounts besides the issuer that may `issue`, by position.
+        /// Removing one moves the last minter into its slot.
+        minters: Mapping<u32, AccountId>,
+        minter_count: u32,
+        minter_positions: Mapping<AccountId, u32>,
+        /// Tokens held in the contract's own account for vesting schedules,
+        /// pending transfers and streams, which `rescue` must not touch.
+        escrowed: Balance,
+        /// Escrowed two-step transfers keyed by `(from, to)`.
+        pending: Mapping<(AccountId, AccountId), Balance>,
+        /// Minimum number of blocks between two transfers by the same account.
+        /// Zero disables the limit.
+        cooldown_blocks: BlockNumber,
+        last_transfer: Mapping<AccountId, BlockNumber>,
+        /// Spenders holding a nonzero allowance from each owner, in the order
+        /// they were first approved. Keeps allowances enumerable per owner.
+        spenders: Mapping<AccountId, Vec<AccountId>>,
+        /// Blocks during which the issuer may reverse a transfer. Zero
+        /// disables clawbacks.
+        clawback_window: BlockNumber,
+        /// Most recent transfer by each sender as `(to, value, block)`.
+        clawbackable: Mapping<AccountId, (AccountId, Balance, BlockNumber)>,
+        /// Smallest nonzero balance a sender may keep after a transfer.
+        min_balance: Balance,
+        /// When false, every event but `Create` is suppressed.
+        events_enabled: bool,
+        /// Share of every transfer routed to the issuer, in basis points.
+        fee_bps: u16,
+        /// Set while a message that calls out to other contracts is running.
+        entered: bool,
+        /// Binds signed payloads such as permits to this token deployment.
+        domain_separator: [u8; 32],
+        /// The latest transfers as `(from, to, value, timestamp)`, in a ring
+        /// of `recent_transfers_cap` slots. `recent_transfers_head` is the
+        /// slot written next and `recent_transfers_len` the number kept.
+        recent_transfers: Mapping<u32, (AccountId, AccountId, Balance, Timestamp)>,
+        recent_transfers_head: u32,
+        recent_transfers_len: u32,
+        recent_transfers_cap: u32,
+        /// Total supply after each change as `(block, total_supply)`, in a ring
+        /// like `recent_transfers`.
+        supply_log: Mapping<u32, (BlockNumber, Balance)>,
+        supply_log_head: u32,
+        supply_log_len: u32,
+        supply_log_cap: u32,
+        /// When set, `transfer` and `transfer_from` only move tokens between
+        /// accounts in `allowed`.
+        allowlist_enabled: bool,
+        allowed: Mapping<AccountId, bool>,
+        /// Sends burned tokens to `dead` instead of reducing `total_supply`.
+        burn_to_address: bool,
+        /// The dead address, `DEAD` unless picked at construction. Shared by
+        /// `burn_to_address` and `auto_burn`.
+        dead: AccountId,
+        /// Burns transfers to `dead` instead of crediting it.
+        auto_burn: bool,
+        /// Part of each balance that cannot be transferred.
+        locked: Mapping<AccountId, Balance>,
+        /// Smallest nonzero amount a single transfer may move.
+        min_transfer: Balance,
+        /// Largest amount a single transfer may move. Zero means unlimited.
+        max_transfer: Balance,
+        /// Rounding of transfer fees and `distribute` shares.
+        rounding_mode: RoundingMode,
+        /// Airdrop rate of each announced snapshot, in basis points of the
+        /// snapshot balance.
+        airdrops: Mapping<u32, u16>,
+        airdrop_claimed: Mapping<(u32, AccountId), bool>,
+        /// When set, only accounts in `approved_spenders` may call
+        /// `transfer_from`.
+        spender_whitelist_enabled: bool,
+        approved_spenders: Mapping<AccountId, bool>,
+        /// Lifetime supply added and removed, the initial supply counting as
+        /// minted, so `total_minted - total_burned` is `total_supply`.
+        total_minted: Balance,
+        total_burned: Balance,
+        /// The only recipient an allowance may be spent to, for allowances
+        /// granted with `approve_to_recipient`.
+        allowance_recipients: Mapping<(AccountId, AccountId), AccountId>,
+        /// Open payment streams by id. Finished and cancelled streams are
+        /// removed.
+        streams: Mapping<u32, Stream>,
+        stream_count: u32,
+        /// A burn leaving less than this behind sweeps the remainder to the
+        /// issuer. Zero disables sweeping.
+        dust_threshold: Balance,
+        /// Basis points of `total_supply` `mint_inflation` mints to the
+        /// issuer once every `period_blocks`. A zero period disables it.
+        inflation_bps_per_period: u16,
+        period_blocks: u64,
+        /// Start of the current inflation period.
+        last_inflation_block: u64,
+        /// Account each delegator lends its voting balance to.
+        delegates: Mapping<AccountId, AccountId>,
+        /// Accounts whose transfers, sent or received, pay no fee.
+        fee_exempt: Mapping<AccountId, bool>,
     }
 
     #[ink(event)]
@@ -29,6 +366,31 @@ mod erc20 {
         value: Balance,
     }
 
+    /// Emitted next to the regular event of `transfer` and `transfer_from`
+    /// for indexers that want the block and the caller inline. `value` is
+    /// what `to` received, after the transfer fee.
+    #[ink(event)]
+    pub struct TransferDetailed {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        block: BlockNumber,
+        caller: AccountId,
+    }
+
+    /// `value` is what `to` received, after the transfer fee.
+    #[ink(event)]
+    pub struct TransferMemo {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        memo: Vec<u8>,
+    }
+
     #[ink(event)]
     pub struct Approval {
         #[ink(topic)]
@@ -56,420 +418,9365 @@ mod erc20 {
         value: Balance,
     }
 
+    /// `issuer` is the account that authorised the issue, `to` the one
+    /// credited.
     #[ink(event)]
     pub struct Issue {
         #[ink(topic)]
         issuer: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Distribution {
+        #[ink(topic)]
+        issuer: AccountId,
+        total: Balance,
+    }
+
+    #[ink(event)]
+    pub struct IssuershipTransferred {
+        #[ink(topic)]
+        previous: AccountId,
+        #[ink(topic)]
+        new: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MinterAdded {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MinterRemoved {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MetadataUpdated {
+        name: Option<String>,
+        symbol: Option<String>,
+    }
+
+    #[ink(event)]
+    pub struct TreasuryChanged {
+        #[ink(topic)]
+        previous: AccountId,
+        #[ink(topic)]
+        new: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct IssuershipRenounced {
+        #[ink(topic)]
+        previous: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        issuer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        issuer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Frozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unfrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyDrain {
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Adjustment {
+        #[ink(topic)]
+        owner: AccountId,
+        old: Balance,
+        new: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ForceTransfer {
+        #[ink(topic)]
+        issuer: AccountId,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Snapshot {
+        id: u32,
+    }
+
+    #[ink(event)]
+    pub struct AirdropAnnounced {
+        snapshot_id: u32,
+        per_token_bps: u16,
+    }
+
+    #[ink(event)]
+    pub struct AirdropClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        snapshot_id: u32,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Proposed {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        proposer: AccountId,
+        action: Action,
+    }
+
+    #[ink(event)]
+    pub struct Confirmed {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        issuer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct IssueQueued {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        issuer: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        ready_at: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct IssueExecuted {
+        #[ink(topic)]
+        id: u32,
+    }
+
+    #[ink(event)]
+    pub struct Clawback {
+        #[ink(topic)]
+        issuer: AccountId,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct VestingCreated {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        total: Balance,
+        start: BlockNumber,
+        duration: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct StreamCreated {
+        id: u32,
+        #[ink(topic)]
+        sender: AccountId,
+        #[ink(topic)]
+        recipient: AccountId,
+        rate_per_block: Balance,
+        duration: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct StreamWithdrawn {
+        id: u32,
+        #[ink(topic)]
+        recipient: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct StreamCancelled {
+        id: u32,
+        paid: Balance,
+        refunded: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: AccountId,
+        previous: Option<AccountId>,
+        #[ink(topic)]
+        new: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct VestingClaimed {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TransferPending {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TransferClaimed {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TransferCancelled {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
         value: Balance,
     }
 
-    #[derive(Debug, PartialEq, Eq, scale::Encode)]
+    /// Errors are SCALE-encoded as their variant index, so clients decode
+    /// them by position: `InsufficientBalance` is `0x00`, `InsufficientAllowance`
+    /// is `0x01`, `NotIssuer` is `0x02` and so on. Existing variants must never
+    /// be reordered; new ones are appended at the end.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
-        InsufficentBalance,
-        InsufficentAllowance,
+        InsufficientBalance,
+        InsufficientAllowance,
         NotIssuer,
+        Overflow,
+        Paused,
+        CapExceeded,
+        AccountFrozen,
+        PermitExpired,
+        InvalidSignature,
+        InvalidSnapshot,
+        ThresholdNotMet,
+        ProposalNotFound,
+        AlreadyConfirmed,
+        TimelockNotElapsed,
+        IssueNotFound,
+        MemoTooLong,
+        CallbackFailed,
+        VestingExists,
+        VestingNotFound,
+        CooldownActive,
+        ClawbackWindowClosed,
+        DustRemainder,
+        BatchTooLarge,
+        InvalidFee,
+        Reentrancy,
+        DuplicateSpender,
+        NotAllowlisted,
+        InvalidPercent,
+        ParseAmount,
+        ApproveFromNonZero,
+        PendingNotFound,
+        NotUnitAligned,
+        NotPaused,
+        DeadlinePassed,
+        BalanceLocked,
+        BelowMinTransfer,
+        AboveMaxTransfer,
+        AirdropNotFound,
+        AlreadyClaimed,
+        SpenderNotWhitelisted,
+        RecipientMismatch,
+        StreamNotFound,
+        NotStreamParty,
+        BadNonce,
+        ZeroAddress,
+        ReceiverRejected,
+        EscrowNotEmpty,
+        InvalidTransferBounds,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
-    impl Erc20 {
-        #[ink(constructor)]
-        pub fn new(total_supply: Balance) -> Self {
-            let caller = Self::env().caller();
-            let mut balances = StorageHashMap::new();
-            balances.insert(caller, total_supply);
-            let instance = Self {
-                issuer: caller,
-                total_supply: total_supply,
-                balances: balances,
-                allowances: StorageHashMap::new(),
-            };
+    /// An issuer action that needs `threshold` confirmations under
+    /// multi-signature control.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Action {
+        Issue(Balance),
+        Pause,
+        Unpause,
+    }
 
-            Self::env().emit_event(Create {
-                from: caller,
-                total_supply: total_supply,
-            });
+    /// How fee and distribution shares are rounded to whole units.
+    #[derive(
+        Debug,
+        Default,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+        SpreadLayout,
+        PackedLayout,
+    )]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+    )]
+    pub enum RoundingMode {
+        #[default]
+        Down,
+        Up,
+        /// Halves round up.
+        Nearest,
+    }
 
-            instance
+    impl SpreadAllocate for RoundingMode {
+        fn allocate_spread(ptr: &mut KeyPtr) -> Self {
+            ptr.advance_by(<Self as SpreadLayout>::FOOTPRINT);
+
+            Self::default()
         }
+    }
 
-        #[ink(message)]
-        pub fn total_supply(&self) -> Balance {
-            self.total_supply
+    impl RoundingMode {
+        /// Rounds the exact result `quotient + remainder / divisor`, where
+        /// `remainder < divisor`.
+        fn round(self, quotient: Balance, remainder: Balance, divisor: Balance) -> Balance {
+            let round_up = match self {
+                RoundingMode::Down => false,
+                RoundingMode::Up => remainder > 0,
+                RoundingMode::Nearest => remainder > 0 && remainder >= divisor - remainder,
+            };
+
+            if round_up {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    }
+
+    /// Tokens escrowed from `sender` that accrue to `recipient` at
+    /// `rate_per_block` for `duration` blocks from `start`.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Stream {
+        pub sender: AccountId,
+        pub recipient: AccountId,
+        pub rate_per_block: Balance,
+        pub start: BlockNumber,
+        pub duration: BlockNumber,
+        pub withdrawn: Balance,
+    }
+
+    impl Stream {
+        fn total(&self) -> Balance {
+            self.rate_per_block * Balance::from(self.duration)
+        }
+
+        /// Everything accrued to the recipient by block `now`, withdrawn or
+        /// not.
+        fn accrued(&self, now: BlockNumber) -> Balance {
+            let elapsed = now.saturating_sub(self.start).min(self.duration);
+
+            self.rate_per_block * Balance::from(elapsed)
+        }
+    }
+
+    /// Every configurable setting of the token, as returned by `get_config`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Config {
+        pub issuer: AccountId,
+        pub name: Option<String>,
+        pub symbol: Option<String>,
+        pub decimals: u8,
+        pub cap: Option<Balance>,
+        pub paused: bool,
+        pub threshold: u32,
+        pub mint_delay: BlockNumber,
+        pub treasury: AccountId,
+        pub unit_size: Balance,
+        pub strict_approve: bool,
+        pub cooldown_blocks: BlockNumber,
+        pub clawback_window: BlockNumber,
+        pub min_balance: Balance,
+        pub events_enabled: bool,
+        pub fee_bps: u16,
+        pub recent_transfers_cap: u32,
+        pub supply_log_cap: u32,
+        pub allowlist_enabled: bool,
+        pub burn_to_address: bool,
+        pub dead: AccountId,
+        pub auto_burn: bool,
+        pub min_transfer: Balance,
+        pub max_transfer: Balance,
+        pub rounding_mode: RoundingMode,
+        pub spender_whitelist_enabled: bool,
+        pub dust_threshold: Balance,
+        pub inflation_bps_per_period: u16,
+        pub period_blocks: u64,
+    }
+
+    /// A pending action. Proposals are removed once executed.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, SpreadLayout, PackedLayout,
+    )]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Proposal {
+        pub action: Action,
+        pub proposer: AccountId,
+        pub confirmations: Vec<AccountId>,
+    }
+
+    impl Erc20 {
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            Self::new_with_metadata(total_supply, None, None, 18)
+        }
+
+        /// Traps if `decimals` exceeds `MAX_DECIMALS`, so a token with
+        /// unusable decimals never deploys.
+        #[ink(constructor)]
+        pub fn new_with_metadata(
+            total_supply: Balance,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
+            assert!(decimals <= MAX_DECIMALS, "decimals exceed MAX_DECIMALS");
+
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                // `SpreadAllocate` gives an `Option` field one cell less than
+                // `SpreadLayout`, so every `Mapping` after one would be keyed
+                // differently from the copy messages load. Storing and
+                // reloading the fresh contract keys them the way messages do.
+                push_spread_root(contract, &ROOT_KEY);
+                *contract = pull_spread_root(&ROOT_KEY);
+
+                let caller = Self::env().caller();
+                contract.issuer = caller;
+                contract.treasury = caller;
+                contract.dead = DEAD.into();
+                contract.name = name;
+                contract.symbol = symbol;
+                contract.decimals = decimals;
+                contract.total_supply = total_supply;
+                contract.total_minted = total_supply;
+                contract.threshold = 1;
+                contract.events_enabled = true;
+                contract.domain_separator = contract.compute_domain_separator();
+                contract.recent_transfers_cap = DEFAULT_RECENT_TRANSFERS_CAP;
+                contract.supply_log_cap = DEFAULT_SUPPLY_LOG_CAP;
+                contract.set_balance(caller, total_supply);
+
+                Self::env().emit_event(Create {
+                    from: caller,
+                    total_supply,
+                });
+            })
+        }
+
+        #[ink(constructor)]
+        pub fn new_capped(total_supply: Balance, cap: Balance) -> Self {
+            assert!(total_supply <= cap, "total supply exceeds cap");
+
+            let mut instance = Self::new(total_supply);
+            instance.cap = Some(cap);
+
+            instance
+        }
+
+        /// Puts issuing and pausing under `threshold`-of-`issuers` control. The
+        /// deployer still holds the supply and the remaining issuer powers.
+        #[ink(constructor)]
+        pub fn new_multisig(
+            total_supply: Balance,
+            issuers: Vec<AccountId>,
+            threshold: u32,
+        ) -> Self {
+            assert!(
+                threshold > 0 && threshold as usize <= issuers.len(),
+                "threshold must be between 1 and the number of issuers"
+            );
+            for (index, issuer) in issuers.iter().enumerate() {
+                assert!(!issuers[..index].contains(issuer), "duplicate issuer");
+            }
+
+            let mut instance = Self::new(total_supply);
+            instance.issuers = issuers;
+            instance.threshold = threshold;
+
+            instance
+        }
+
+        /// Funds each account in `allocations` at deploy time; the total supply
+        /// is their sum.
+        #[ink(constructor)]
+        pub fn new_with_allocations(allocations: Vec<(AccountId, Balance)>) -> Self {
+            let mut total_supply: Balance = 0;
+            for (index, (account, value)) in allocations.iter().enumerate() {
+                assert!(
+                    !allocations[..index].iter().any(|(a, _)| a == account),
+                    "duplicate allocation"
+                );
+                total_supply = total_supply
+                    .checked_add(*value)
+                    .expect("total supply overflow");
+            }
+
+            // The whole supply starts with the caller and is handed out from
+            // there, so the caller keeps only what it allocated to itself.
+            let mut instance = Self::new(total_supply);
+            let caller = Self::env().caller();
+            for (account, value) in allocations {
+                instance
+                    .move_balance(caller, account, value)
+                    .expect("allocations add up to the total supply");
+
+                instance.emit(Transfer {
+                    from: Self::zero_account(),
+                    to: account,
+                    value,
+                });
+            }
+
+            instance
+        }
+
+        /// Keeps the last `cap` transfers for `recent_transfers`; zero keeps
+        /// none.
+        #[ink(constructor)]
+        pub fn new_with_recent_transfers_cap(total_supply: Balance, cap: u32) -> Self {
+            let mut instance = Self::new(total_supply);
+            instance.recent_transfers_cap = cap;
+
+            instance
+        }
+
+        /// Keeps the last `cap` supply changes for `supply_log`; zero keeps
+        /// none.
+        #[ink(constructor)]
+        pub fn new_with_supply_log_cap(total_supply: Balance, cap: u32) -> Self {
+            let mut instance = Self::new(total_supply);
+            instance.supply_log_cap = cap;
+
+            instance
+        }
+
+        /// Picks whether `burn` moves tokens to the dead address, leaving
+        /// `total_supply` as is, or destroys them. The dead address is `DEAD`
+        /// here; `new_with_auto_burn` is the constructor that picks another.
+        #[ink(constructor)]
+        pub fn new_with_burn_to_address(total_supply: Balance, burn_to_address: bool) -> Self {
+            let mut instance = Self::new(total_supply);
+            instance.burn_to_address = burn_to_address;
+
+            instance
+        }
+
+        /// Treats every transfer to `dead`, whether by `transfer`,
+        /// `transfer_from` or `transfer_batch`, as a burn, so the tokens leave
+        /// `total_supply` rather than pile up there. `dead` is also where
+        /// `burn` sends tokens when `burn_to_address` is set.
+        #[ink(constructor)]
+        pub fn new_with_auto_burn(total_supply: Balance, dead: AccountId) -> Self {
+            let mut instance = Self::new(total_supply);
+            instance.dead = dead;
+            instance.auto_burn = true;
+
+            instance
+        }
+
+        /// Delays every issue by `mint_delay` blocks; queued issues are
+        /// applied with `execute_issue`.
+        #[ink(constructor)]
+        pub fn new_with_mint_delay(total_supply: Balance, mint_delay: BlockNumber) -> Self {
+            let mut instance = Self::new(total_supply);
+            instance.mint_delay = mint_delay;
+
+            instance
+        }
+
+        #[ink(message)]
+        pub fn token_name(&self) -> Option<String> {
+            self.name.clone()
+        }
+
+        #[ink(message)]
+        pub fn token_symbol(&self) -> Option<String> {
+            self.symbol.clone()
+        }
+
+        #[ink(message)]
+        pub fn token_decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn dead_address(&self) -> AccountId {
+            self.dead
+        }
+
+        #[ink(message)]
+        pub fn total_minted(&self) -> Balance {
+            self.total_minted
+        }
+
+        #[ink(message)]
+        pub fn total_burned(&self) -> Balance {
+            self.total_burned
+        }
+
+        /// Total supply minus whatever sits in the treasury.
+        #[ink(message)]
+        pub fn circulating_supply(&self) -> Balance {
+            self.total_supply - self.balance_of(self.treasury)
+        }
+
+        #[ink(message)]
+        pub fn treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        #[ink(message)]
+        pub fn cooldown_blocks(&self) -> BlockNumber {
+            self.cooldown_blocks
+        }
+
+        #[ink(message)]
+        pub fn clawback_window(&self) -> BlockNumber {
+            self.clawback_window
+        }
+
+        #[ink(message)]
+        pub fn min_balance(&self) -> Balance {
+            self.min_balance
+        }
+
+        #[ink(message)]
+        pub fn min_transfer(&self) -> Balance {
+            self.min_transfer
+        }
+
+        #[ink(message)]
+        pub fn max_transfer(&self) -> Balance {
+            self.max_transfer
+        }
+
+        #[ink(message)]
+        pub fn strict_approve(&self) -> bool {
+            self.strict_approve
+        }
+
+        #[ink(message)]
+        pub fn unit_size(&self) -> Balance {
+            self.unit_size
+        }
+
+        #[ink(message)]
+        pub fn events_enabled(&self) -> bool {
+            self.events_enabled
+        }
+
+        #[ink(message)]
+        pub fn fee_bps(&self) -> u16 {
+            self.fee_bps
+        }
+
+        #[ink(message)]
+        pub fn rounding_mode(&self) -> RoundingMode {
+            self.rounding_mode
+        }
+
+        #[ink(message)]
+        pub fn dust_threshold(&self) -> Balance {
+            self.dust_threshold
+        }
+
+        #[ink(message)]
+        pub fn inflation_bps_per_period(&self) -> u16 {
+            self.inflation_bps_per_period
+        }
+
+        #[ink(message)]
+        pub fn period_blocks(&self) -> u64 {
+            self.period_blocks
+        }
+
+        #[ink(message)]
+        pub fn last_inflation_block(&self) -> u64 {
+            self.last_inflation_block
+        }
+
+        #[ink(message)]
+        pub fn allowlist_enabled(&self) -> bool {
+            self.allowlist_enabled
+        }
+
+        #[ink(message)]
+        pub fn is_allowed(&self, account: AccountId) -> bool {
+            self.allowed.get(account).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn is_fee_exempt(&self, account: AccountId) -> bool {
+            self.fee_exempt.get(account).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn spender_whitelist_enabled(&self) -> bool {
+            self.spender_whitelist_enabled
+        }
+
+        #[ink(message)]
+        pub fn is_whitelisted_spender(&self, account: AccountId) -> bool {
+            self.approved_spenders.get(account).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn cap(&self) -> Option<Balance> {
+            self.cap
         }
 
         #[ink(message)]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
-            *self.balances.get(&owner).unwrap_or(&0)
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// The balance of `owner` for clients limited to 64-bit integers.
+        /// Fails with `Error::Overflow` rather than truncating.
+        #[ink(message)]
+        pub fn balance_of_u64(&self, owner: AccountId) -> Result<u64> {
+            u64::try_from(self.balance_of(owner)).map_err(|_| Error::Overflow)
+        }
+
+        /// The raw balance of `owner` together with `decimals`, which is all a
+        /// client needs to render it.
+        #[ink(message)]
+        pub fn balance_of_scaled(&self, owner: AccountId) -> (Balance, u8) {
+            (self.balance_of(owner), self.decimals)
+        }
+
+        /// `amount` rescaled from `decimals` to `target_decimals`, rounded down
+        /// when scaling to fewer decimals.
+        #[ink(message)]
+        pub fn to_base_units(&self, amount: Balance, target_decimals: u8) -> Result<Balance> {
+            if target_decimals >= self.decimals {
+                let factor = Balance::from(10u8)
+                    .checked_pow(u32::from(target_decimals - self.decimals))
+                    .ok_or(Error::Overflow)?;
+
+                amount.checked_mul(factor).ok_or(Error::Overflow)
+            } else {
+                // Any factor beyond `Balance::MAX` would floor `amount` to zero.
+                let factor =
+                    Balance::from(10u8).checked_pow(u32::from(self.decimals - target_decimals));
+
+                Ok(factor.map_or(0, |factor| amount / factor))
+            }
+        }
+
+        /// Quadratic voting weight: the square root of the balance of
+        /// `account`, rounded down.
+        #[ink(message)]
+        pub fn voting_power(&self, account: AccountId) -> u128 {
+            Self::isqrt(self.balance_of(account))
+        }
+
+        #[ink(message)]
+        pub fn delegate_of(&self, account: AccountId) -> Option<AccountId> {
+            self.delegates.get(account)
+        }
+
+        /// Sum of the balances of every account that delegated to `account`,
+        /// including `account` itself once it delegates to itself. Walks the
+        /// whole account index.
+        #[ink(message)]
+        pub fn delegated_power(&self, account: AccountId) -> Balance {
+            self.known_accounts()
+                .filter(|delegator| self.delegate_of(*delegator) == Some(account))
+                .map(|delegator| self.balance_of(delegator))
+                .sum()
+        }
+
+        /// Balances of `accounts`, in the same order.
+        #[ink(message)]
+        pub fn balances_of(&self, accounts: Vec<AccountId>) -> Result<Vec<Balance>> {
+            if accounts.len() > MAX_BATCH_LEN {
+                return Err(Error::BatchTooLarge);
+            }
+
+            Ok(accounts
+                .into_iter()
+                .map(|account| self.balance_of(account))
+                .collect())
+        }
+
+        #[ink(message)]
+        pub fn balance_of_at(&self, owner: AccountId, snapshot_id: u32) -> Result<Balance> {
+            let checkpoints = self.balance_snapshots.get(owner).unwrap_or_default();
+
+            self.value_at(&checkpoints, snapshot_id, self.balance_of(owner))
+        }
+
+        #[ink(message)]
+        pub fn total_supply_at(&self, snapshot_id: u32) -> Result<Balance> {
+            self.ensure_snapshot_taken(snapshot_id)?;
+
+            Ok((snapshot_id..=self.total_supply_checkpoint)
+                .find_map(|id| self.total_supply_snapshots.get(id))
+                .unwrap_or(self.total_supply))
+        }
+
+        #[ink(message)]
+        pub fn holder_count(&self) -> u32 {
+            self.holder_count
+        }
+
+        /// The `n` largest holders, largest first, with ties in first-credit
+        /// order. `n` is capped at `MAX_TOP_HOLDERS`. This loads and sorts
+        /// every holder, O(holders log holders), so it is meant for off-chain
+        /// queries rather than other contracts.
+        #[ink(message)]
+        pub fn top_holders(&self, n: u32) -> Vec<(AccountId, Balance)> {
+            let mut holders: Vec<(AccountId, Balance)> = self
+                .known_accounts()
+                .map(|account| (account, self.balance_of(account)))
+                .filter(|(_, balance)| *balance > 0)
+                .collect();
+            holders.sort_by_key(|(_, balance)| core::cmp::Reverse(*balance));
+            holders.truncate(n.min(MAX_TOP_HOLDERS) as usize);
+
+            holders
+        }
+
+        /// Length of the account index paged by `holders_page`: every account
+        /// ever credited, drained or not.
+        #[ink(message)]
+        pub fn account_count(&self) -> u32 {
+            self.account_count
+        }
+
+        /// Holders with a nonzero balance among the `limit` accounts at
+        /// positions `start..` of the account index, in first-credit order.
+        /// Drained accounts are skipped, so a page can come back short or
+        /// empty; scanning is done once `start` reaches `account_count`.
+        /// `limit` is capped at `MAX_BATCH_LEN`.
+        #[ink(message)]
+        pub fn holders_page(&self, start: u32, limit: u32) -> Vec<(AccountId, Balance)> {
+            let end = start
+                .saturating_add(limit.min(MAX_BATCH_LEN as u32))
+                .min(self.account_count);
+
+            (start..end)
+                .filter_map(|index| self.accounts.get(index))
+                .map(|account| (account, self.balance_of(account)))
+                .filter(|(_, balance)| *balance > 0)
+                .collect()
+        }
+
+        /// Sum of every balance, for reconciling against `total_supply`
+        /// off-chain. Walks the whole account index.
+        #[ink(message)]
+        pub fn sum_balances(&self) -> Result<Balance> {
+            self.known_accounts()
+                .try_fold(0, |sum: Balance, account| {
+                    sum.checked_add(self.balance_of(account))
+                })
+                .ok_or(Error::Overflow)
+        }
+
+        #[ink(message)]
+        pub fn is_frozen(&self, account: AccountId) -> bool {
+            self.frozen.get(account).unwrap_or(false)
+        }
+
+        /// `(paused, frozen)`: whether the token is paused and whether
+        /// `account` is frozen, in one read.
+        #[ink(message)]
+        pub fn status(&self, account: AccountId) -> (bool, bool) {
+            (self.paused, self.is_frozen(account))
+        }
+
+        /// All configurable settings in one read.
+        #[ink(message)]
+        pub fn get_config(&self) -> Config {
+            Config {
+                issuer: self.issuer,
+                name: self.name.clone(),
+                symbol: self.symbol.clone(),
+                decimals: self.decimals,
+                cap: self.cap,
+                paused: self.paused,
+                threshold: self.threshold,
+                mint_delay: self.mint_delay,
+                treasury: self.treasury,
+                unit_size: self.unit_size,
+                strict_approve: self.strict_approve,
+                cooldown_blocks: self.cooldown_blocks,
+                clawback_window: self.clawback_window,
+                min_balance: self.min_balance,
+                events_enabled: self.events_enabled,
+                fee_bps: self.fee_bps,
+                recent_transfers_cap: self.recent_transfers_cap,
+                supply_log_cap: self.supply_log_cap,
+                allowlist_enabled: self.allowlist_enabled,
+                burn_to_address: self.burn_to_address,
+                dead: self.dead,
+                auto_burn: self.auto_burn,
+                min_transfer: self.min_transfer,
+                max_transfer: self.max_transfer,
+                rounding_mode: self.rounding_mode,
+                spender_whitelist_enabled: self.spender_whitelist_enabled,
+                dust_threshold: self.dust_threshold,
+                inflation_bps_per_period: self.inflation_bps_per_period,
+                period_blocks: self.period_blocks,
+            }
+        }
+
+        #[ink(message)]
+        pub fn locked_of(&self, account: AccountId) -> Balance {
+            self.locked.get(account).unwrap_or_default()
+        }
+
+        /// Up to `limit` of the latest transfers, newest first.
+        #[ink(message)]
+        pub fn recent_transfers(
+            &self,
+            limit: u32,
+        ) -> Vec<(AccountId, AccountId, Balance, Timestamp)> {
+            Self::latest_in_ring(
+                &self.recent_transfers,
+                self.recent_transfers_head,
+                self.recent_transfers_len,
+                self.recent_transfers_cap,
+                limit,
+            )
+        }
+
+        /// Up to `limit` of the latest supply changes as `(block, total_supply)`,
+        /// newest first. The initial supply is only recorded by `Create`.
+        #[ink(message)]
+        pub fn supply_log(&self, limit: u32) -> Vec<(BlockNumber, Balance)> {
+            Self::latest_in_ring(
+                &self.supply_log,
+                self.supply_log_head,
+                self.supply_log_len,
+                self.supply_log_cap,
+                limit,
+            )
+        }
+
+        /// Whether `account` has ever been credited, even if its balance has
+        /// since dropped back to zero.
+        #[ink(message)]
+        pub fn has_interacted(&self, account: AccountId) -> bool {
+            self.seen.get(account).unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.approve_help(who, spender, value)
+        }
+
+        #[ink(message)]
+        pub fn approve_capped(&mut self, spender: AccountId, value: Balance) -> Result<Balance> {
+            let who = Self::env().caller();
+
+            self.approve_capped_help(who, spender, value)
+        }
+
+        #[ink(message)]
+        pub fn approve_to_recipient(
+            &mut self,
+            spender: AccountId,
+            recipient: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.approve_to_recipient_help(who, spender, recipient, value)
+        }
+
+        #[ink(message)]
+        pub fn approve_and_notify(
+            &mut self,
+            spender: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.approve_and_notify_help(who, spender, value, data)
+        }
+
+        #[ink(message)]
+        pub fn approve_batch(&mut self, approvals: Vec<(AccountId, Balance)>) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.approve_batch_help(who, approvals)
+        }
+
+        #[ink(message)]
+        pub fn revoke_all_allowances(&mut self) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.revoke_all_allowances_help(who)
+        }
+
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        /// The recipient the allowance of `spender` from `owner` is bound to,
+        /// if any.
+        #[ink(message)]
+        pub fn allowance_recipient(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+        ) -> Option<AccountId> {
+            self.allowance_recipients.get((owner, spender))
+        }
+
+        /// Every spender `owner` has a nonzero allowance for, with its amount.
+        #[ink(message)]
+        pub fn allowances_of(&self, owner: AccountId) -> Vec<(AccountId, Balance)> {
+            self.spenders
+                .get(owner)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|spender| (spender, self.allowance(owner, spender)))
+                .collect()
+        }
+
+        /// What `owner` holds beyond everything it has approved, or 0 once
+        /// its allowances add up to more than its balance.
+        #[ink(message)]
+        pub fn free_balance(&self, owner: AccountId) -> Balance {
+            let approved = self
+                .allowances_of(owner)
+                .into_iter()
+                .fold(0, |total: Balance, (_, value)| total.saturating_add(value));
+
+            self.balance_of(owner).saturating_sub(approved)
+        }
+
+        /// How much `spender` can move out of `owner` right now: the smaller of
+        /// the allowance and the owner's balance.
+        #[ink(message)]
+        pub fn spendable_by(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowance(owner, spender).min(self.balance_of(owner))
+        }
+
+        /// Whether `spender` could move `value` out of `owner` right now: the
+        /// token is not paused, neither account is frozen, the spender passes
+        /// the whitelist, and both the allowance and the unlocked balance
+        /// cover `value`. Allowances never expire. Checks on the recipient are
+        /// left out, since it is not known here.
+        #[ink(message)]
+        pub fn can_spend(&self, owner: AccountId, spender: AccountId, value: Balance) -> bool {
+            self.ensure_transferable(owner, spender).is_ok()
+                && (!self.spender_whitelist_enabled || self.is_whitelisted_spender(spender))
+                && self.ensure_unlocked(owner, value).is_ok()
+                && self.spendable_by(owner, spender) >= value
+        }
+
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let who = Self::env().caller();
+            let allowance = self
+                .allowance(who, spender)
+                .checked_add(delta)
+                .ok_or(Error::Overflow)?;
+
+            self.set_approval(who, spender, allowance)
+        }
+
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let who = Self::env().caller();
+            let allowance = self
+                .allowance(who, spender)
+                .checked_sub(delta)
+                .ok_or(Error::InsufficientAllowance)?;
+
+            self.set_approval(who, spender, allowance)
+        }
+
+        #[ink(message)]
+        pub fn nonce_of(&self, owner: AccountId) -> u64 {
+            self.nonces.get(owner).unwrap_or_default()
+        }
+
+        /// Prefix for every signed payload, so a signature for one token
+        /// deployment cannot be replayed against another.
+        #[ink(message)]
+        pub fn domain_separator(&self) -> [u8; 32] {
+            self.domain_separator
+        }
+
+        /// Returns the hash `owner` has to sign for the next `permit`.
+        #[ink(message)]
+        pub fn permit_hash(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            deadline: Timestamp,
+        ) -> [u8; 32] {
+            Self::env().hash_encoded::<Blake2x256, _>(&(
+                self.domain_separator,
+                owner,
+                spender,
+                value,
+                self.nonce_of(owner),
+                deadline,
+            ))
+        }
+
+        #[ink(message)]
+        pub fn permit(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            deadline: Timestamp,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            self.permit_help(owner, spender, value, deadline, signature)
+        }
+
+        /// Returns the hash `from` has to sign for a relayed
+        /// `transfer_with_sig` using `nonce`.
+        #[ink(message)]
+        pub fn transfer_with_sig_hash(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            nonce: u64,
+        ) -> [u8; 32] {
+            Self::env().hash_encoded::<Blake2x256, _>(&(
+                self.domain_separator,
+                b"transfer",
+                from,
+                to,
+                value,
+                nonce,
+            ))
+        }
+
+        /// Moves `value` from `from` to `to` on behalf of a relayer, given
+        /// `from`'s signature over `transfer_with_sig_hash`. The nonce must
+        /// equal `nonce_of(from)` and is consumed by the transfer.
+        #[ink(message)]
+        pub fn transfer_with_sig(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            self.transfer_with_sig_help(from, to, value, nonce, signature)
+        }
+
+        /// Lends the caller's balance to `to` for `delegated_power`. The
+        /// tokens do not move.
+        #[ink(message)]
+        pub fn delegate(&mut self, to: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.delegate_help(who, to)
+        }
+
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_help(who, to, value)
+        }
+
+        /// The balances of `from` and `to` after `from` transferred `value` to
+        /// `to`, or the error that transfer would fail with. Includes the
+        /// transfer fee and `auto_burn`, and leaves storage untouched.
+        #[ink(message)]
+        pub fn simulate_transfer(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(Balance, Balance)> {
+            self.check_transfer(from, to, value)?;
+
+            let fee = self.fee_on(from, to, value);
+            let balance_after = |account: AccountId| {
+                let mut balance = self.balance_of(account);
+                if account == from {
+                    balance -= value;
+                }
+                if account == to && !self.burns_on_arrival(to) {
+                    balance += value - fee;
+                }
+                if account == self.issuer {
+                    balance += fee;
+                }
+                balance
+            };
+
+            Ok((balance_after(from), balance_after(to)))
+        }
+
+        /// `transfer` with the amount given as a base-10 string of digits.
+        #[ink(message)]
+        pub fn transfer_str(&mut self, to: AccountId, amount: String) -> Result<()> {
+            let who = Self::env().caller();
+            let value = Self::parse_amount(&amount)?;
+
+            self.transfer_help(who, to, value)
+        }
+
+        #[ink(message)]
+        pub fn transfer_all(&mut self, to: AccountId) -> Result<Balance> {
+            let who = Self::env().caller();
+
+            self.transfer_all_help(who, to)
+        }
+
+        #[ink(message)]
+        pub fn transfer_deadline(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            deadline_block: BlockNumber,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_deadline_help(who, to, value, deadline_block)
+        }
+
+        #[ink(message)]
+        pub fn transfer_with_memo(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            memo: Vec<u8>,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_with_memo_help(who, to, value, memo)
+        }
+
+        #[ink(message)]
+        pub fn transfer_and_call(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_and_call_help(who, to, value, data)
+        }
+
+        #[ink(message)]
+        pub fn safe_transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.safe_transfer_help(who, to, value)
+        }
+
+        #[ink(message)]
+        pub fn transfer_batch(&mut self, recipients: Vec<(AccountId, Balance)>) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_batch_help(who, recipients)
+        }
+
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_from_help(who, from, to, value)
+        }
+
+        /// `transfer_from` that returns the caller's remaining allowance from
+        /// `from`, which stays `Balance::MAX` for unlimited allowances.
+        #[ink(message)]
+        pub fn transfer_from_returning(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<Balance> {
+            let who = Self::env().caller();
+
+            self.transfer_from_help(who, from, to, value)?;
+
+            Ok(self.allowance(from, who))
+        }
+
+        #[ink(message)]
+        pub fn self_transfer_from(
+            &mut self,
+            spender: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.self_transfer_from_help(who, spender, to, value)
+        }
+
+        #[ink(message)]
+        pub fn burn(&mut self, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.burn_help(who, value)
+        }
+
+        #[ink(message)]
+        pub fn burn_percent(&mut self, bps: u16) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.burn_percent_help(who, bps)
+        }
+
+        #[ink(message)]
+        pub fn burn_from(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.burn_from_help(who, from, value)
+        }
+
+        #[ink(message)]
+        pub fn issue(&mut self, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.issue_help(who, value)
+        }
+
+        #[ink(message)]
+        pub fn issue_to(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.issue_to_help(who, to, value)
+        }
+
+        #[ink(message)]
+        pub fn add_minter(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.add_minter_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn remove_minter(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.remove_minter_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn distribute(&mut self, total: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.distribute_help(who, total)
+        }
+
+        #[ink(message)]
+        pub fn mint_delay(&self) -> BlockNumber {
+            self.mint_delay
+        }
+
+        #[ink(message)]
+        pub fn pending_issue(&self, id: u32) -> Option<(AccountId, Balance, BlockNumber)> {
+            self.pending_issues
+                .get(id)
+                .map(|(_, to, value, ready_at)| (to, value, ready_at))
+        }
+
+        #[ink(message)]
+        pub fn execute_issue(&mut self, id: u32) -> Result<()> {
+            self.execute_issue_help(id)
+        }
+
+        #[ink(message)]
+        pub fn transfer_issuership(&mut self, new_issuer: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_issuership_help(who, new_issuer)
+        }
+
+        #[ink(message)]
+        pub fn set_name(&mut self, name: String) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_name_help(who, name)
+        }
+
+        #[ink(message)]
+        pub fn set_symbol(&mut self, symbol: String) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_symbol_help(who, symbol)
+        }
+
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_treasury_help(who, treasury)
+        }
+
+        #[ink(message)]
+        pub fn set_cooldown(&mut self, cooldown_blocks: BlockNumber) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_cooldown_help(who, cooldown_blocks)
+        }
+
+        #[ink(message)]
+        pub fn set_clawback_window(&mut self, clawback_window: BlockNumber) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_clawback_window_help(who, clawback_window)
+        }
+
+        #[ink(message)]
+        pub fn set_min_balance(&mut self, min_balance: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_min_balance_help(who, min_balance)
+        }
+
+        #[ink(message)]
+        pub fn set_min_transfer(&mut self, min_transfer: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_min_transfer_help(who, min_transfer)
+        }
+
+        #[ink(message)]
+        pub fn set_max_transfer(&mut self, max_transfer: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_max_transfer_help(who, max_transfer)
+        }
+
+        #[ink(message)]
+        pub fn set_strict_approve(&mut self, strict_approve: bool) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_strict_approve_help(who, strict_approve)
+        }
+
+        #[ink(message)]
+        pub fn set_unit_size(&mut self, unit_size: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_unit_size_help(who, unit_size)
+        }
+
+        #[ink(message)]
+        pub fn set_events_enabled(&mut self, events_enabled: bool) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_events_enabled_help(who, events_enabled)
+        }
+
+        #[ink(message)]
+        pub fn set_fee_bps(&mut self, fee_bps: u16) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_fee_bps_help(who, fee_bps)
+        }
+
+        #[ink(message)]
+        pub fn set_rounding_mode(&mut self, rounding_mode: RoundingMode) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_rounding_mode_help(who, rounding_mode)
+        }
+
+        #[ink(message)]
+        pub fn set_dust_threshold(&mut self, dust_threshold: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_dust_threshold_help(who, dust_threshold)
+        }
+
+        /// Configures the inflation schedule. The first period starts now.
+        #[ink(message)]
+        pub fn set_inflation(
+            &mut self,
+            inflation_bps_per_period: u16,
+            period_blocks: u64,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_inflation_help(who, inflation_bps_per_period, period_blocks)
+        }
+
+        /// Mints one period of inflation to the issuer. Anyone may call it
+        /// once the period has elapsed.
+        #[ink(message)]
+        pub fn mint_inflation(&mut self) -> Result<Balance> {
+            self.mint_inflation_help()
+        }
+
+        #[ink(message)]
+        pub fn set_allowlist_enabled(&mut self, allowlist_enabled: bool) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_allowlist_enabled_help(who, allowlist_enabled)
+        }
+
+        #[ink(message)]
+        pub fn allow(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.allow_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn disallow(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.disallow_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn set_fee_exempt(&mut self, account: AccountId, exempt: bool) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_fee_exempt_help(who, account, exempt)
+        }
+
+        #[ink(message)]
+        pub fn set_spender_whitelist_enabled(
+            &mut self,
+            spender_whitelist_enabled: bool,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.set_spender_whitelist_enabled_help(who, spender_whitelist_enabled)
+        }
+
+        #[ink(message)]
+        pub fn whitelist_spender(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.whitelist_spender_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn unwhitelist_spender(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.unwhitelist_spender_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn renounce_issuership(&mut self) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.renounce_issuership_help(who)
+        }
+
+        #[ink(message)]
+        pub fn terminate(&mut self) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.terminate_help(who)
+        }
+
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.pause_help(who)
+        }
+
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.unpause_help(who)
+        }
+
+        #[ink(message)]
+        pub fn snapshot(&mut self) -> Result<u32> {
+            let who = Self::env().caller();
+
+            self.snapshot_help(who)
+        }
+
+        #[ink(message)]
+        pub fn announce_airdrop(&mut self, snapshot_id: u32, per_token_bps: u16) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.announce_airdrop_help(who, snapshot_id, per_token_bps)
+        }
+
+        #[ink(message)]
+        pub fn claim_airdrop(&mut self, snapshot_id: u32, per_token_bps: u16) -> Result<Balance> {
+            let who = Self::env().caller();
+
+            self.claim_airdrop_help(who, snapshot_id, per_token_bps)
+        }
+
+        #[ink(message)]
+        pub fn airdrop_rate(&self, snapshot_id: u32) -> Option<u16> {
+            self.airdrops.get(snapshot_id)
+        }
+
+        #[ink(message)]
+        pub fn has_claimed_airdrop(&self, snapshot_id: u32, account: AccountId) -> bool {
+            self.airdrop_claimed
+                .get((snapshot_id, account))
+                .unwrap_or(false)
+        }
+
+        #[ink(message)]
+        pub fn issuer(&self) -> AccountId {
+            self.issuer
+        }
+
+        #[ink(message)]
+        pub fn issuers(&self) -> Vec<AccountId> {
+            self.issuers.clone()
+        }
+
+        #[ink(message)]
+        pub fn minters(&self) -> Vec<AccountId> {
+            (0..self.minter_count)
+                .filter_map(|position| self.minters.get(position))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn is_minter(&self, account: AccountId) -> bool {
+            self.minter_positions.contains(account)
+        }
+
+        #[ink(message)]
+        pub fn threshold(&self) -> u32 {
+            self.threshold
+        }
+
+        #[ink(message)]
+        pub fn proposals(&self) -> Vec<(u32, Proposal)> {
+            (0..self.proposal_count)
+                .filter_map(|id| self.proposals.get(id).map(|proposal| (id, proposal)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn propose_issue(&mut self, value: Balance) -> Result<u32> {
+            let who = Self::env().caller();
+
+            self.propose_help(who, Action::Issue(value))
+        }
+
+        #[ink(message)]
+        pub fn propose_pause(&mut self) -> Result<u32> {
+            let who = Self::env().caller();
+
+            self.propose_help(who, Action::Pause)
+        }
+
+        #[ink(message)]
+        pub fn propose_unpause(&mut self) -> Result<u32> {
+            let who = Self::env().caller();
+
+            self.propose_help(who, Action::Unpause)
+        }
+
+        #[ink(message)]
+        pub fn confirm(&mut self, proposal_id: u32) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.confirm_help(who, proposal_id)
+        }
+
+        #[ink(message)]
+        pub fn freeze(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.freeze_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn unfreeze(&mut self, account: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.unfreeze_help(who, account)
+        }
+
+        #[ink(message)]
+        pub fn lock(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.lock_help(who, account, amount)
+        }
+
+        #[ink(message)]
+        pub fn unlock(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.unlock_help(who, account, amount)
+        }
+
+        #[ink(message)]
+        pub fn force_transfer(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.force_transfer_help(who, from, to, value)
+        }
+
+        #[ink(message)]
+        pub fn rescue(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.rescue_help(who, to, value)
+        }
+
+        #[ink(message)]
+        pub fn merge_accounts(&mut self, from: AccountId, into: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.merge_accounts_help(who, from, into)
+        }
+
+        #[ink(message)]
+        pub fn reconcile(&mut self, owner: AccountId, correct_balance: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.reconcile_help(who, owner, correct_balance)
+        }
+
+        #[ink(message)]
+        pub fn emergency_withdraw_all(&mut self, to: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.emergency_withdraw_all_help(who, to)
+        }
+
+        #[ink(message)]
+        pub fn clawback(&mut self, original_sender: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.clawback_help(who, original_sender)
+        }
+
+        #[ink(message)]
+        pub fn create_vesting(
+            &mut self,
+            beneficiary: AccountId,
+            total: Balance,
+            duration: BlockNumber,
+        ) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.create_vesting_help(who, beneficiary, total, duration)
+        }
+
+        #[ink(message)]
+        pub fn vesting_of(
+            &self,
+            beneficiary: AccountId,
+        ) -> Option<(Balance, BlockNumber, BlockNumber)> {
+            self.vesting.get(beneficiary)
+        }
+
+        #[ink(message)]
+        pub fn claim_vested(&mut self) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.claim_vested_help(who)
+        }
+
+        #[ink(message)]
+        pub fn create_stream(
+            &mut self,
+            to: AccountId,
+            rate_per_block: Balance,
+            duration: BlockNumber,
+        ) -> Result<u32> {
+            let who = Self::env().caller();
+
+            self.create_stream_help(who, to, rate_per_block, duration)
+        }
+
+        #[ink(message)]
+        pub fn withdraw_stream(&mut self, stream_id: u32) -> Result<Balance> {
+            let who = Self::env().caller();
+
+            self.withdraw_stream_help(who, stream_id)
+        }
+
+        #[ink(message)]
+        pub fn cancel_stream(&mut self, stream_id: u32) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.cancel_stream_help(who, stream_id)
+        }
+
+        #[ink(message)]
+        pub fn stream(&self, stream_id: u32) -> Option<Stream> {
+            self.streams.get(stream_id)
+        }
+
+        #[ink(message)]
+        pub fn pending_transfer(&self, from: AccountId, to: AccountId) -> Balance {
+            self.pending.get((from, to)).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn transfer_pending(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.transfer_pending_help(who, to, value)
+        }
+
+        #[ink(message)]
+        pub fn claim_pending(&mut self, from: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.claim_pending_help(from, who)
+        }
+
+        #[ink(message)]
+        pub fn cancel_pending(&mut self, to: AccountId) -> Result<()> {
+            let who = Self::env().caller();
+
+            self.cancel_pending_help(who, to)
+        }
+
+        /// The all-zero account nobody holds a key for. It is the counterparty
+        /// of `Transfer` events for mints and burns and the renounced issuer.
+        fn zero_account() -> AccountId {
+            [0x0; 32].into()
+        }
+
+        /// Zero balances are removed rather than stored, so drained accounts
+        /// do not keep occupying a storage cell. Every balance write goes
+        /// through here, which keeps `holder_count` in step with the map.
+        fn set_balance(&mut self, owner: AccountId, value: Balance) {
+            let previous = self.balance_of(owner);
+            if self.snapshot_id > 0 {
+                let mut checkpoints = self.balance_snapshots.get(owner).unwrap_or_default();
+                if Self::record_checkpoint(&mut checkpoints, self.snapshot_id, previous) {
+                    self.balance_snapshots.insert(owner, &checkpoints);
+                }
+            }
+
+            if value > 0 && !self.seen.get(owner).unwrap_or(false) {
+                self.seen.insert(owner, &true);
+                self.accounts.insert(self.account_count, &owner);
+                self.account_count += 1;
+            }
+
+            if previous == 0 && value > 0 {
+                self.holder_count += 1;
+            } else if previous > 0 && value == 0 {
+                self.holder_count -= 1;
+            }
+
+            if value == 0 {
+                self.balances.remove(owner);
+            } else {
+                self.balances.insert(owner, &value);
+            }
+        }
+
+        /// Central allowance write, which also keeps `spenders` in sync.
+        fn set_allowance(&mut self, owner: AccountId, spender: AccountId, value: Balance) {
+            let previous = self.allowance(owner, spender);
+            if previous == 0 && value > 0 {
+                let mut spenders = self.spenders.get(owner).unwrap_or_default();
+                spenders.push(spender);
+                self.spenders.insert(owner, &spenders);
+            } else if previous > 0 && value == 0 {
+                let mut spenders = self.spenders.get(owner).unwrap_or_default();
+                spenders.retain(|s| *s != spender);
+                if spenders.is_empty() {
+                    self.spenders.remove(owner);
+                } else {
+                    self.spenders.insert(owner, &spenders);
+                }
+            }
+
+            // A zero allowance is deleted rather than stored, which frees the
+            // storage whenever an allowance is used up or revoked.
+            if value == 0 {
+                self.allowances.remove((owner, spender));
+                self.allowance_recipients.remove((owner, spender));
+            } else {
+                self.allowances.insert((owner, spender), &value);
+            }
+        }
+
+        /// Moves `value` between two balances the caller has already checked.
+        /// Both new balances are computed before either is written, and a
+        /// self-transfer leaves the balance untouched.
+        fn move_balance(&mut self, from: AccountId, to: AccountId, value: Balance) -> Result<()> {
+            if from == to {
+                return Ok(());
+            }
+
+            let from_balance = self
+                .balance_of(from)
+                .checked_sub(value)
+                .ok_or(Error::Overflow)?;
+            let to_balance = self
+                .balance_of(to)
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            self.set_balance(from, from_balance);
+            self.set_balance(to, to_balance);
+
+            Ok(())
+        }
+
+        /// Checks that `sum_balances` matches `total_supply`. Only compiled
+        /// with the `audit` feature. ink! cannot `cfg`-gate messages, so this
+        /// is an inherent method for audit builds and off-chain tests rather
+        /// than a message.
+        #[cfg(feature = "audit")]
+        pub fn verify_supply_invariant(&self) -> bool {
+            self.sum_balances() == Ok(self.total_supply)
+        }
+
+        /// Every account that has ever been credited, in first-credit order.
+        fn known_accounts(&self) -> impl Iterator<Item = AccountId> + '_ {
+            (0..self.account_count).filter_map(move |index| self.accounts.get(index))
+        }
+
+        fn parse_amount(amount: &str) -> Result<Balance> {
+            if !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(Error::ParseAmount);
+            }
+
+            amount.parse().map_err(|_| Error::ParseAmount)
+        }
+
+        /// Emits `event` unless the issuer has switched events off.
+        fn emit<E>(&self, event: E)
+        where
+            E: Into<<Self as ink_lang::reflect::ContractEventBase>::Type>,
+        {
+            if self.events_enabled {
+                Self::env().emit_event(event);
+            }
+        }
+
+        /// Entry half of the reentrancy guard around messages that call out to
+        /// other contracts. The lock only holds once it is in storage, so
+        /// these messages `flush` before every call out. Read-only messages
+        /// never take the lock.
+        fn enter(&mut self) -> Result<()> {
+            if self.entered {
+                return Err(Error::Reentrancy);
+            }
+
+            self.entered = true;
+
+            Ok(())
+        }
+
+        fn exit(&mut self) {
+            self.entered = false;
+        }
+
+        /// Writes the contract's fields to storage ahead of a call to another
+        /// contract. ink! otherwise only stores them once the message returns,
+        /// so a message re-entering the token would load the state from before
+        /// this one, reentrancy lock included.
+        fn flush(&self) {
+            push_spread_root(self, &ROOT_KEY);
+        }
+
+        /// Reads the contract's fields back after a call to another contract,
+        /// picking up whatever a message re-entering the token changed.
+        /// Writing back the copy from before the call would undo it.
+        fn reload(&mut self) {
+            *self = pull_spread_root(&ROOT_KEY);
+        }
+
+        fn emit_transfer_detailed(&self, from: AccountId, to: AccountId, value: Balance) {
+            self.emit(TransferDetailed {
+                from,
+                to,
+                value,
+                block: Self::env().block_number(),
+                caller: Self::env().caller(),
+            });
+        }
+
+        fn emit_metadata_updated(&self) {
+            self.emit(MetadataUpdated {
+                name: self.name.clone(),
+                symbol: self.symbol.clone(),
+            });
+        }
+
+        /// The environment exposes no chain id, so the contract account, which
+        /// is unique to one deployment on one chain, stands in for it.
+        fn compute_domain_separator(&self) -> [u8; 32] {
+            Self::env().hash_encoded::<Blake2x256, _>(&(
+                b"erc20",
+                &self.name,
+                Self::env().account_id(),
+            ))
+        }
+
+        /// Checks shared by every message that moves or approves tokens.
+        fn ensure_transferable(&self, from: AccountId, to: AccountId) -> Result<()> {
+            if self.paused {
+                return Err(Error::Paused);
+            }
+
+            if self.is_frozen(from) || self.is_frozen(to) {
+                return Err(Error::AccountFrozen);
+            }
+
+            Ok(())
+        }
+
+        /// Tokens sent to the zero account could never be moved again; `burn`
+        /// is the way to destroy them.
+        fn ensure_not_zero(to: AccountId) -> Result<()> {
+            if to == Self::zero_account() {
+                return Err(Error::ZeroAddress);
+            }
+
+            Ok(())
+        }
+
+        fn ensure_allowlisted(&self, from: AccountId, to: AccountId) -> Result<()> {
+            if self.allowlist_enabled && !(self.is_allowed(from) && self.is_allowed(to)) {
+                return Err(Error::NotAllowlisted);
+            }
+
+            Ok(())
+        }
+
+        /// Zero-value transfers are no-ops and are not bounded.
+        fn ensure_within_transfer_bounds(&self, value: Balance) -> Result<()> {
+            if value < self.min_transfer {
+                return Err(Error::BelowMinTransfer);
+            }
+
+            if self.max_transfer > 0 && value > self.max_transfer {
+                return Err(Error::AboveMaxTransfer);
+            }
+
+            Ok(())
+        }
+
+        fn ensure_unlocked(&self, owner: AccountId, value: Balance) -> Result<()> {
+            if self.balance_of(owner).saturating_sub(self.locked_of(owner)) < value {
+                return Err(Error::BalanceLocked);
+            }
+
+            Ok(())
+        }
+
+        fn ensure_unit_aligned(&self, value: Balance) -> Result<()> {
+            if self.unit_size > 1 && !value.is_multiple_of(self.unit_size) {
+                return Err(Error::NotUnitAligned);
+            }
+
+            Ok(())
+        }
+
+        fn ensure_cooldown_elapsed(&self, from: AccountId) -> Result<()> {
+            if self.cooldown_blocks == 0 {
+                return Ok(());
+            }
+
+            if let Some(last) = self.last_transfer.get(from) {
+                if Self::env().block_number() - last < self.cooldown_blocks {
+                    return Err(Error::CooldownActive);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Rejects a debit of `value` that would leave `from` with a nonzero
+        /// balance below `min_balance`. Draining the account entirely is fine.
+        fn ensure_no_dust(&self, from: AccountId, value: Balance) -> Result<()> {
+            let remainder = self.balance_of(from).saturating_sub(value);
+            if remainder > 0 && remainder < self.min_balance {
+                return Err(Error::DustRemainder);
+            }
+
+            Ok(())
+        }
+
+        fn record_clawbackable(&mut self, from: AccountId, to: AccountId, value: Balance) {
+            if self.clawback_window > 0 {
+                self.clawbackable
+                    .insert(from, &(to, value, Self::env().block_number()));
+            }
+        }
+
+        fn record_recent_transfer(&mut self, from: AccountId, to: AccountId, value: Balance) {
+            Self::push_ring(
+                &mut self.recent_transfers,
+                &mut self.recent_transfers_head,
+                &mut self.recent_transfers_len,
+                self.recent_transfers_cap,
+                &(from, to, value, Self::env().block_timestamp()),
+            );
+        }
+
+        /// Writes `entry` to slot `head` of a ring of `cap` slots, which holds
+        /// the oldest entry once `len` reaches `cap`. Each entry costs a
+        /// single storage write, however large the ring.
+        fn push_ring<T: PackedLayout + scale::EncodeLike>(
+            ring: &mut Mapping<u32, T>,
+            head: &mut u32,
+            len: &mut u32,
+            cap: u32,
+            entry: &T,
+        ) {
+            if cap == 0 {
+                return;
+            }
+
+            ring.insert(*head, entry);
+            *head = (*head + 1) % cap;
+            *len = (*len + 1).min(cap);
+        }
+
+        /// Up to `limit` entries of a ring written by `push_ring`, newest
+        /// first.
+        fn latest_in_ring<T: PackedLayout>(
+            ring: &Mapping<u32, T>,
+            head: u32,
+            len: u32,
+            cap: u32,
+            limit: u32,
+        ) -> Vec<T> {
+            (1..=limit.min(len))
+                .filter_map(|age| {
+                    let slot = if age <= head {
+                        head - age
+                    } else {
+                        cap - (age - head)
+                    };
+                    ring.get(slot)
+                })
+                .collect()
+        }
+
+        fn record_transfer(&mut self, from: AccountId) {
+            if self.cooldown_blocks > 0 {
+                self.last_transfer.insert(from, &Self::env().block_number());
+            }
+        }
+
+        /// Whether tokens sent to `to` are burned rather than credited, which
+        /// `auto_burn` does for the dead address.
+        fn burns_on_arrival(&self, to: AccountId) -> bool {
+            self.auto_burn && to == self.dead
+        }
+
+        /// Moves `value` from `from` to `to` and emits `Transfer`, once the
+        /// caller has run the transfer guards. Burns it instead when `to` is
+        /// the dead address under `auto_burn`. Returns the amount left after
+        /// the fee. `transfer`, `transfer_batch` and `transfer_from` all
+        /// move tokens through here.
+        fn settle_transfer(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<Balance> {
+            if self.balance_of(from) < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let fee = self.fee_on(from, to, value);
+            if fee > 0 {
+                let issuer = self.issuer;
+                self.move_balance(from, issuer, fee)?;
+
+                self.emit(Transfer {
+                    from,
+                    to: issuer,
+                    value: fee,
+                });
+            }
+
+            let value = value - fee;
+            if self.burns_on_arrival(to) {
+                self.destroy(from, value)?;
+
+                return Ok(value);
+            }
+
+            self.move_balance(from, to, value)?;
+            self.record_clawbackable(from, to, value);
+            self.record_recent_transfer(from, to, value);
+
+            self.emit(Transfer { from, to, value });
+
+            Ok(value)
+        }
+
+        /// The issuer's cut of a `value` transfer, rounded by `rounding_mode`.
+        /// The fee never exceeds `value`, so the fee and the remainder always
+        /// add up to `value`. Once the issuership is renounced there is nobody
+        /// to pay, so no fee is taken. Neither is one when `from` or `to` is
+        /// fee-exempt, or when the transfer is burned on arrival.
+        fn fee_on(&self, from: AccountId, to: AccountId, value: Balance) -> Balance {
+            if self.issuer == Self::zero_account()
+                || self.is_fee_exempt(from)
+                || self.is_fee_exempt(to)
+                || self.burns_on_arrival(to)
+            {
+                return 0;
+            }
+
+            Self::bps_of(value, self.fee_bps, self.rounding_mode)
+        }
+
+        /// `value * bps / BASIS_POINTS`, rounded by `mode`. Split by the
+        /// quotient and remainder of `BASIS_POINTS` so it cannot overflow.
+        fn bps_of(value: Balance, bps: u16, mode: RoundingMode) -> Balance {
+            let bps = Balance::from(bps);
+            let max = Balance::from(BASIS_POINTS);
+            let scaled = value % max * bps;
+
+            mode.round(value / max * bps + scaled / max, scaled % max, max)
+        }
+
+        /// `value * numerator / divisor` as `(quotient, remainder)`. The product
+        /// is kept in 256 bits, so only a quotient that does not fit a
+        /// `Balance` fails, as does a zero `divisor`.
+        fn mul_div(
+            value: Balance,
+            numerator: Balance,
+            divisor: Balance,
+        ) -> Option<(Balance, Balance)> {
+            if divisor == 0 {
+                return None;
+            }
+
+            // Schoolbook product of the 64-bit halves, as `high * 2^128 + low`.
+            let half = u128::from(u64::MAX);
+            let (value_high, value_low) = (value >> 64, value & half);
+            let (numerator_high, numerator_low) = (numerator >> 64, numerator & half);
+            let (cross, cross_carry) =
+                (value_low * numerator_high).overflowing_add(value_high * numerator_low);
+            let (low, low_carry) = (value_low * numerator_low).overflowing_add(cross << 64);
+            let high = value_high * numerator_high
+                + (cross >> 64)
+                + (u128::from(cross_carry) << 64)
+                + u128::from(low_carry);
+
+            if high >= divisor {
+                return None;
+            }
+
+            // Long division, shifting in one bit of `low` at a time.
+            let mut quotient: Balance = 0;
+            let mut remainder = high;
+            for bit in (0..128).rev() {
+                let carry = remainder >> 127;
+                remainder = (remainder << 1) | ((low >> bit) & 1);
+                quotient <<= 1;
+                if carry == 1 || remainder >= divisor {
+                    remainder = remainder.wrapping_sub(divisor);
+                    quotient |= 1;
+                }
+            }
+
+            Some((quotient, remainder))
+        }
+
+        /// Floor of the square root of `value`, by Newton's method. The guess
+        /// starts above the root and decreases until it stops improving.
+        fn isqrt(value: u128) -> u128 {
+            if value < 2 {
+                return value;
+            }
+
+            // `(value + 1) / 2`, which cannot overflow.
+            let mut root = value;
+            let mut next = value / 2 + value % 2;
+            while next < root {
+                root = next;
+                next = (root + value / root) / 2;
+            }
+
+            root
+        }
+
+        /// Every supply change goes through here, which keeps `total_minted`
+        /// and `total_burned` in step. Repeated minting and burning could in
+        /// principle push either counter past `Balance::MAX`, so they saturate
+        /// rather than abort the supply change.
+        fn set_total_supply(&mut self, value: Balance) {
+            if value >= self.total_supply {
+                self.total_minted = self.total_minted.saturating_add(value - self.total_supply);
+            } else {
+                self.total_burned = self.total_burned.saturating_add(self.total_supply - value);
+            }
+
+            if self.snapshot_id > self.total_supply_checkpoint {
+                self.total_supply_snapshots
+                    .insert(self.snapshot_id, &self.total_supply);
+                self.total_supply_checkpoint = self.snapshot_id;
+            }
+
+            self.total_supply = value;
+            Self::push_ring(
+                &mut self.supply_log,
+                &mut self.supply_log_head,
+                &mut self.supply_log_len,
+                self.supply_log_cap,
+                &(Self::env().block_number(), value),
+            );
+        }
+
+        /// Snapshots are written lazily: the first change after snapshot `id`
+        /// stores the value as it was at `id`. Returns whether a checkpoint was
+        /// added.
+        fn record_checkpoint(
+            checkpoints: &mut Vec<(u32, Balance)>,
+            id: u32,
+            value: Balance,
+        ) -> bool {
+            match checkpoints.last() {
+                Some((last, _)) if *last >= id => false,
+                _ => {
+                    checkpoints.push((id, value));
+                    true
+                }
+            }
+        }
+
+        fn ensure_snapshot_taken(&self, id: u32) -> Result<()> {
+            if id == 0 || id > self.snapshot_id {
+                return Err(Error::InvalidSnapshot);
+            }
+
+            Ok(())
+        }
+
+        /// The value at snapshot `id` is held by the first checkpoint taken at
+        /// or after `id`; without one it has not changed since and `current`
+        /// still applies.
+        fn value_at(
+            &self,
+            checkpoints: &[(u32, Balance)],
+            id: u32,
+            current: Balance,
+        ) -> Result<Balance> {
+            self.ensure_snapshot_taken(id)?;
+
+            Ok(checkpoints
+                .iter()
+                .find(|(checkpoint, _)| *checkpoint >= id)
+                .map_or(current, |(_, value)| *value))
+        }
+
+        pub fn transfer_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.check_transfer(from, to, value)?;
+
+            if value == 0 {
+                return Ok(());
+            }
+
+            #[cfg(feature = "hooks")]
+            ActiveHook::default().before_transfer(from, to, value);
+
+            let received = self.settle_transfer(from, to, value)?;
+            self.record_transfer(from);
+            if !self.burns_on_arrival(to) {
+                self.emit_transfer_detailed(from, to, received);
+            }
+
+            #[cfg(feature = "hooks")]
+            ActiveHook::default().after_transfer(from, to, value);
+
+            Ok(())
+        }
+
+        /// Every check `transfer_help` runs before moving any tokens, shared
+        /// with `simulate_transfer`.
+        fn check_transfer(&self, from: AccountId, to: AccountId, value: Balance) -> Result<()> {
+            Self::ensure_not_zero(to)?;
+            self.ensure_transferable(from, to)?;
+            self.ensure_allowlisted(from, to)?;
+            self.ensure_unit_aligned(value)?;
+
+            if value == 0 {
+                return Ok(());
+            }
+
+            self.ensure_within_transfer_bounds(value)?;
+            self.ensure_cooldown_elapsed(from)?;
+            self.ensure_no_dust(from, value)?;
+
+            if self.balance_of(from) < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.ensure_unlocked(from, value)
+        }
+
+        /// ERC-677 style transfer: moves the tokens, then calls
+        /// `on_token_transfer(from, value, data)` on `to` if it is a contract.
+        ///
+        /// The balances are written before the external call, so a callee that
+        /// re-enters the token already observes the completed transfer. The
+        /// message holds the reentrancy lock throughout. If the callback fails
+        /// the whole message is reverted, returning `Error::CallbackFailed`.
+        pub fn transfer_and_call_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.enter()?;
+
+            let result = self
+                .transfer_help(from, to, value)
+                .and_then(|()| self.notify_recipient(from, to, value, data));
+
+            self.exit();
+
+            result
+        }
+
+        /// Calls `on_token_transfer` on `to` if it is a contract. A failing
+        /// callee fails the message, which reverts it as a whole.
+        fn notify_recipient(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            let callee = ActiveCallee::default();
+            if !callee.is_contract(to) {
+                return Ok(());
+            }
+
+            self.flush();
+            let succeeded = callee.on_token_transfer(to, from, value, data);
+            self.reload();
+
+            if !succeeded {
+                return Err(Error::CallbackFailed);
+            }
+
+            Ok(())
+        }
+
+        /// `transfer`, except that a contract recipient has to accept the
+        /// tokens, so they are not stranded in a contract that cannot move
+        /// them. Such a recipient must implement the `ERC20Receiver`
+        /// interface, a message `on_erc20_received(from: AccountId, value:
+        /// Balance) -> [u8; 4]` returning `ERC20_RECEIVED`.
+        ///
+        /// The recipient is asked once the transfer checks pass but before
+        /// any balance moves, so it does not see the tokens yet. If the call
+        /// fails or returns anything else, nothing changes and the message
+        /// returns `Error::ReceiverRejected`. The message holds the reentrancy
+        /// lock throughout. Plain accounts are never called.
+        pub fn safe_transfer_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.enter()?;
+
+            let result = self
+                .check_transfer(from, to, value)
+                .and_then(|()| self.ensure_receiver_accepts(from, to, value))
+                .and_then(|()| self.transfer_help(from, to, value));
+
+            self.exit();
+
+            result
+        }
+
+        /// Calls `on_erc20_received` on `to` if it is a contract, failing
+        /// unless it returns `ERC20_RECEIVED`.
+        fn ensure_receiver_accepts(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let callee = ActiveCallee::default();
+            if !callee.is_contract(to) {
+                return Ok(());
+            }
+
+            self.flush();
+            let answer = callee.on_erc20_received(to, from, value);
+            self.reload();
+
+            if answer != Some(ERC20_RECEIVED) {
+                return Err(Error::ReceiverRejected);
+            }
+
+            Ok(())
+        }
+
+        /// `approve`, then calls `receive_approval(owner, value, data)` on
+        /// `spender` if it is a contract, so it can pull the tokens within the
+        /// same transaction.
+        ///
+        /// The callee is allowed to re-enter the token for that, and sees the
+        /// new allowance since it is written before the call. The token reloads
+        /// its state afterwards, so what the callee did is kept. The message
+        /// holds the reentrancy lock throughout. If the callback fails the
+        /// whole message, approval included, is reverted, returning
+        /// `Error::CallbackFailed`.
+        pub fn approve_and_notify_help(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            self.enter()?;
+
+            let result = self
+                .approve_help(owner, spender, value)
+                .and_then(|()| self.notify_spender(owner, spender, value, data));
+
+            self.exit();
+
+            result
+        }
+
+        /// Calls `receive_approval` on `spender` if it is a contract. A failing
+        /// callee fails the message, which reverts it as a whole.
+        fn notify_spender(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            let callee = ActiveCallee::default();
+            if !callee.is_contract(spender) {
+                return Ok(());
+            }
+
+            self.flush();
+            let succeeded = callee.receive_approval(spender, owner, value, data);
+            self.reload();
+
+            if !succeeded {
+                return Err(Error::CallbackFailed);
+            }
+
+            Ok(())
+        }
+
+        /// Transfers the whole balance of `from`, read in the same call, and
+        /// returns the amount sent. An empty account sends nothing and emits
+        /// no event.
+        pub fn transfer_all_help(&mut self, from: AccountId, to: AccountId) -> Result<Balance> {
+            let value = self.balance_of(from);
+
+            if value == 0 {
+                return Ok(0);
+            }
+
+            self.transfer_help(from, to, value)?;
+
+            Ok(value)
+        }
+
+        /// `transfer`, unless the call lands after block `deadline_block`.
+        pub fn transfer_deadline_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            deadline_block: BlockNumber,
+        ) -> Result<()> {
+            if Self::env().block_number() > deadline_block {
+                return Err(Error::DeadlinePassed);
+            }
+
+            self.transfer_help(from, to, value)
+        }
+
+        pub fn transfer_with_memo_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            memo: Vec<u8>,
+        ) -> Result<()> {
+            if memo.len() > MAX_MEMO_LEN {
+                return Err(Error::MemoTooLong);
+            }
+
+            let fee = self.fee_on(from, to, value);
+            self.transfer_help(from, to, value)?;
+
+            if value > 0 {
+                self.emit(TransferMemo {
+                    from,
+                    to,
+                    value: value - fee,
+                    memo,
+                });
+            }
+
+            Ok(())
+        }
+
+        pub fn transfer_batch_help(
+            &mut self,
+            from: AccountId,
+            recipients: Vec<(AccountId, Balance)>,
+        ) -> Result<()> {
+            let mut total: Balance = 0;
+            for (to, value) in recipients.iter() {
+                Self::ensure_not_zero(*to)?;
+                self.ensure_transferable(from, *to)?;
+                self.ensure_allowlisted(from, *to)?;
+                self.ensure_unit_aligned(*value)?;
+                if *value > 0 {
+                    self.ensure_within_transfer_bounds(*value)?;
+                }
+                total = total.checked_add(*value).ok_or(Error::Overflow)?;
+            }
+
+            if self.balance_of(from) < total {
+                return Err(Error::InsufficientBalance);
+            }
+
+            if total == 0 {
+                return Ok(());
+            }
+
+            self.ensure_unlocked(from, total)?;
+
+            // A batch counts as a single transfer for the cooldown and the
+            // dust check.
+            self.ensure_cooldown_elapsed(from)?;
+            self.ensure_no_dust(from, total)?;
+
+            // Every leg passed the transfer guards, the caller can cover the
+            // whole batch and no balance can exceed `total_supply`, so none
+            // of the individual transfers can fail.
+            for (to, value) in recipients {
+                if value > 0 {
+                    self.settle_transfer(from, to, value)?;
+                }
+            }
+            self.record_transfer(from);
+
+            Ok(())
+        }
+
+        pub fn approve_help(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.ensure_strict_approve(owner, spender, value)?;
+
+            self.set_approval(owner, spender, value)?;
+            self.allowance_recipients.remove((owner, spender));
+
+            Ok(())
+        }
+
+        /// `approve`, with the allowance only spendable by `transfer_from` to
+        /// `recipient`. A later `approve` lifts the restriction; changing the
+        /// amount with `increase_allowance` or `decrease_allowance` keeps it.
+        pub fn approve_to_recipient_help(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            recipient: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.approve_help(owner, spender, value)?;
+
+            if value > 0 {
+                self.allowance_recipients
+                    .insert((owner, spender), &recipient);
+            }
+
+            Ok(())
+        }
+
+        /// Spending an allowance bound to a recipient anywhere else, burning
+        /// included, fails with `Error::RecipientMismatch`.
+        fn ensure_allowed_recipient(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+            to: AccountId,
+        ) -> Result<()> {
+            match self.allowance_recipient(owner, spender) {
+                Some(recipient) if recipient != to => Err(Error::RecipientMismatch),
+                _ => Ok(()),
+            }
+        }
+
+        /// `approve`, with `value` capped at the balance of `owner`. Returns the
+        /// allowance actually set.
+        pub fn approve_capped_help(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) -> Result<Balance> {
+            let value = value.min(self.balance_of(owner));
+
+            self.approve_help(owner, spender, value)?;
+
+            Ok(value)
+        }
+
+        /// In strict mode an allowance can only be set from zero or to zero, so
+        /// a spender cannot race a change and spend both the old and the new
+        /// value. `increase_allowance` and `decrease_allowance` are exempt.
+        fn ensure_strict_approve(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if self.strict_approve && value > 0 && self.allowance(owner, spender) > 0 {
+                return Err(Error::ApproveFromNonZero);
+            }
+
+            Ok(())
+        }
+
+        fn set_approval(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.ensure_transferable(owner, spender)?;
+
+            self.set_allowance(owner, spender, value);
+
+            self.emit(Approval {
+                owner,
+                spender,
+                value,
+            });
+
+            Ok(())
+        }
+
+        pub fn approve_batch_help(
+            &mut self,
+            owner: AccountId,
+            approvals: Vec<(AccountId, Balance)>,
+        ) -> Result<()> {
+            for (index, (spender, value)) in approvals.iter().enumerate() {
+                if approvals[..index].iter().any(|(s, _)| s == spender) {
+                    return Err(Error::DuplicateSpender);
+                }
+                self.ensure_transferable(owner, *spender)?;
+                self.ensure_strict_approve(owner, *spender, *value)?;
+            }
+
+            for (spender, value) in approvals {
+                self.approve_help(owner, spender, value)?;
+            }
+
+            Ok(())
+        }
+
+        /// Sets every allowance `owner` has granted back to zero. This works
+        /// while paused or frozen, so a compromised owner can always cut off
+        /// its spenders.
+        pub fn revoke_all_allowances_help(&mut self, owner: AccountId) -> Result<()> {
+            for spender in self.spenders.get(owner).unwrap_or_default() {
+                self.set_allowance(owner, spender, 0);
+
+                self.emit(Approval {
+                    owner,
+                    spender,
+                    value: 0,
+                });
+            }
+
+            Ok(())
+        }
+
+        pub fn permit_help(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            deadline: Timestamp,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            if Self::env().block_timestamp() > deadline {
+                return Err(Error::PermitExpired);
+            }
+
+            let message_hash = self.permit_hash(owner, spender, value, deadline);
+            if Self::recover_signer(&signature, &message_hash)? != owner {
+                return Err(Error::InvalidSignature);
+            }
+
+            let nonce = self.nonce_of(owner).checked_add(1).ok_or(Error::Overflow)?;
+            self.approve_help(owner, spender, value)?;
+            self.nonces.insert(owner, &nonce);
+
+            Ok(())
+        }
+
+        pub fn transfer_with_sig_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            if nonce != self.nonce_of(from) {
+                return Err(Error::BadNonce);
+            }
+
+            let message_hash = self.transfer_with_sig_hash(from, to, value, nonce);
+            if Self::recover_signer(&signature, &message_hash)? != from {
+                return Err(Error::InvalidSignature);
+            }
+
+            let next = nonce.checked_add(1).ok_or(Error::Overflow)?;
+            self.transfer_help(from, to, value)?;
+            self.nonces.insert(from, &next);
+
+            Ok(())
+        }
+
+        pub fn delegate_help(&mut self, from: AccountId, to: AccountId) -> Result<()> {
+            let previous = self.delegate_of(from);
+            self.delegates.insert(from, &to);
+
+            self.emit(DelegateChanged {
+                delegator: from,
+                previous,
+                new: to,
+            });
+
+            Ok(())
+        }
+
+        /// Accounts are derived from ECDSA keys as the Blake2x256 hash of the
+        /// compressed public key, matching Substrate's `MultiSigner::Ecdsa`.
+        fn recover_signer(signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<AccountId> {
+            let public_key = Self::env()
+                .ecdsa_recover(signature, message_hash)
+                .map_err(|_| Error::InvalidSignature)?;
+
+            Ok(Self::env().hash_bytes::<Blake2x256>(&public_key).into())
+        }
+
+        pub fn transfer_from_help(
+            &mut self,
+            from: AccountId,
+            owner: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.delegated_transfer(from, owner, to, value, true)
+        }
+
+        /// Moves `value` from `owner` to `to` on behalf of `from`, with every
+        /// check `transfer_from` makes. The allowance of `from` is only
+        /// consulted and spent when `spend_allowance` is set.
+        fn delegated_transfer(
+            &mut self,
+            from: AccountId,
+            owner: AccountId,
+            to: AccountId,
+            value: Balance,
+            spend_allowance: bool,
+        ) -> Result<()> {
+            self.check_transfer(owner, to, value)?;
+
+            if self.is_frozen(from) {
+                return Err(Error::AccountFrozen);
+            }
+
+            if self.spender_whitelist_enabled && !self.is_whitelisted_spender(from) {
+                return Err(Error::SpenderNotWhitelisted);
+            }
+
+            if value == 0 {
+                return Ok(());
+            }
+
+            let allowance = self.allowance(owner, from);
+
+            if spend_allowance {
+                if allowance < value {
+                    return Err(Error::InsufficientAllowance);
+                }
+
+                self.ensure_allowed_recipient(owner, from, to)?;
+            }
+
+            let received = self.settle_transfer(owner, to, value)?;
+            self.record_transfer(owner);
+
+            // A `Balance::MAX` allowance is treated as unlimited and never
+            // decremented, which saves a storage write per delegated transfer.
+            if spend_allowance && allowance != Balance::MAX {
+                let allowance = allowance.checked_sub(value).ok_or(Error::Overflow)?;
+                self.set_allowance(owner, from, allowance);
+            }
+
+            self.emit(TransferFrom {
+                from,
+                owner,
+                to,
+                value,
+            });
+            if !self.burns_on_arrival(to) {
+                self.emit_transfer_detailed(owner, to, received);
+            }
+
+            Ok(())
+        }
+
+        /// Moves `value` from `owner` to `to` as a `transfer_from` by
+        /// `spender`, so an approve-then-pull flow takes one transaction. The
+        /// owner authorises it directly, so whatever allowance `spender` holds
+        /// is left untouched.
+        pub fn self_transfer_from_help(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.delegated_transfer(spender, owner, to, value, false)
+        }
+
+        pub fn burn_help(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let from_balance = self.balance_of(from);
+
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.ensure_unlocked(from, value)?;
+
+            if self.burn_to_address {
+                // Burning to the dead address is a transfer, and is held back
+                // the same way.
+                let dead = self.dead;
+                self.ensure_transferable(from, dead)?;
+                self.move_balance(from, dead, value)?;
+
+                self.emit(Transfer {
+                    from,
+                    to: dead,
+                    value,
+                });
+            } else {
+                self.destroy(from, value)?;
+            }
+
+            self.sweep_dust(from)
+        }
+
+        /// Moves what is left of the balance of `from` to the issuer when it
+        /// is nonzero but below `dust_threshold`. Locked tokens stay put, and
+        /// once the issuership is renounced there is nobody to sweep to.
+        fn sweep_dust(&mut self, from: AccountId) -> Result<()> {
+            let issuer = self.issuer;
+            let balance = self.balance_of(from);
+            if from == issuer || issuer == Self::zero_account() || balance >= self.dust_threshold {
+                return Ok(());
+            }
+
+            let remainder = balance.saturating_sub(self.locked_of(from));
+            if remainder == 0 {
+                return Ok(());
+            }
+
+            self.move_balance(from, issuer, remainder)?;
+
+            self.emit(Transfer {
+                from,
+                to: issuer,
+                value: remainder,
+            });
+
+            Ok(())
+        }
+
+        /// Removes `value` from the balance of `from` and from the supply.
+        fn destroy(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let from_balance = self
+                .balance_of(from)
+                .checked_sub(value)
+                .ok_or(Error::Overflow)?;
+            let total_supply = self
+                .total_supply()
+                .checked_sub(value)
+                .ok_or(Error::Overflow)?;
+            self.set_balance(from, from_balance);
+            self.set_total_supply(total_supply);
+
+            self.emit(Burn { from, value });
+            self.emit(Transfer {
+                from,
+                to: Self::zero_account(),
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Burns `bps` basis points of the balance of `from`, rounded down.
+        pub fn burn_percent_help(&mut self, from: AccountId, bps: u16) -> Result<()> {
+            if bps > BASIS_POINTS {
+                return Err(Error::InvalidPercent);
+            }
+
+            let value = Self::bps_of(self.balance_of(from), bps, RoundingMode::Down);
+
+            self.burn_help(from, value)
+        }
+
+        pub fn burn_from_help(
+            &mut self,
+            spender: AccountId,
+            owner: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let allowance = self.allowance(owner, spender);
+
+            if allowance < value {
+                return Err(Error::InsufficientAllowance);
+            }
+
+            self.ensure_allowed_recipient(owner, spender, Self::zero_account())?;
+
+            let allowance = allowance.checked_sub(value).ok_or(Error::Overflow)?;
+            self.burn_help(owner, value)?;
+            self.set_allowance(owner, spender, allowance);
+
+            self.emit(Approval {
+                owner,
+                spender,
+                value: allowance,
+            });
+
+            Ok(())
+        }
+
+        pub fn issue_help(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            self.ensure_minter(from)?;
+
+            self.issue_or_queue(from, from, value)
+        }
+
+        /// Like `issue_help`, but credits `to` instead of the issuer.
+        pub fn issue_to_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.ensure_minter(from)?;
+
+            self.issue_or_queue(from, to, value)
+        }
+
+        /// Minters issue on their own, even when the issuer role needs several
+        /// confirmations.
+        fn ensure_minter(&self, from: AccountId) -> Result<()> {
+            if self.is_minter(from) {
+                return Ok(());
+            }
+
+            self.ensure_issuer_action(from)
+        }
+
+        pub fn add_minter_help(&mut self, from: AccountId, account: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if !self.is_minter(account) {
+                let position = self.minter_count;
+                self.minter_count = position.checked_add(1).ok_or(Error::Overflow)?;
+                self.minters.insert(position, &account);
+                self.minter_positions.insert(account, &position);
+
+                self.emit(MinterAdded { account });
+            }
+
+            Ok(())
+        }
+
+        pub fn remove_minter_help(&mut self, from: AccountId, account: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if let Some(position) = self.minter_positions.get(account) {
+                let last = self.minter_count - 1;
+                if let Some(moved) = self.minters.get(last).filter(|_| position != last) {
+                    self.minters.insert(position, &moved);
+                    self.minter_positions.insert(moved, &position);
+                }
+                self.minters.remove(last);
+                self.minter_positions.remove(account);
+                self.minter_count = last;
+
+                self.emit(MinterRemoved { account });
+            }
+
+            Ok(())
+        }
+
+        /// Mints immediately without a `mint_delay`, otherwise queues the issue
+        /// until `mint_delay` blocks have passed. `issuer` is who authorised
+        /// it, for the events.
+        fn issue_or_queue(
+            &mut self,
+            issuer: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if self.mint_delay == 0 {
+                return self.mint(issuer, to, value);
+            }
+
+            // Reject issues that could never be executed before queueing them.
+            self.checked_issue(to, value)?;
+
+            let ready_at = Self::env()
+                .block_number()
+                .checked_add(self.mint_delay)
+                .ok_or(Error::Overflow)?;
+            let id = self.pending_issue_count;
+            self.pending_issue_count = id.checked_add(1).ok_or(Error::Overflow)?;
+            self.pending_issues
+                .insert(id, &(issuer, to, value, ready_at));
+
+            self.emit(IssueQueued {
+                id,
+                issuer,
+                to,
+                value,
+                ready_at,
+            });
+
+            Ok(())
+        }
+
+        pub fn execute_issue_help(&mut self, id: u32) -> Result<()> {
+            let (issuer, to, value, ready_at) =
+                self.pending_issues.get(id).ok_or(Error::IssueNotFound)?;
+
+            if Self::env().block_number() < ready_at {
+                return Err(Error::TimelockNotElapsed);
+            }
+
+            self.mint(issuer, to, value)?;
+            self.pending_issues.remove(id);
+
+            self.emit(IssueExecuted { id });
+
+            Ok(())
+        }
+
+        /// The balance of `to` and the total supply after issuing `value`.
+        /// Both additions are checked, so an issue can never wrap either
+        /// around.
+        fn checked_issue(&self, to: AccountId, value: Balance) -> Result<(Balance, Balance)> {
+            let to_balance = self
+                .balance_of(to)
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            let total_supply = self
+                .total_supply()
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+
+            if let Some(cap) = self.cap {
+                if total_supply > cap {
+                    return Err(Error::CapExceeded);
+                }
+            }
+
+            Ok((to_balance, total_supply))
+        }
+
+        fn mint(&mut self, issuer: AccountId, to: AccountId, value: Balance) -> Result<()> {
+            let (to_balance, total_supply) = self.checked_issue(to, value)?;
+
+            self.set_balance(to, to_balance);
+            self.set_total_supply(total_supply);
+
+            self.emit(Issue { issuer, to, value });
+            self.emit(Transfer {
+                from: Self::zero_account(),
+                to,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Mints `total` and credits every holder `total * balance / total_supply`,
+        /// rounded by `rounding_mode`. Accounts with a zero balance, and holders whose share
+        /// rounds down to zero, receive nothing; the rounding dust goes to the
+        /// issuer. Under a `mint_delay` every share is queued as a pending
+        /// issue instead, computed from the balances at the time of the call.
+        /// The contract's own account and the dead address take no part: their
+        /// balances leave `total_supply` for the weights and earn no share.
+        pub fn distribute_help(&mut self, from: AccountId, total: Balance) -> Result<()> {
+            self.ensure_issuer_action(from)?;
+
+            let total_supply = self
+                .total_supply
+                .checked_add(total)
+                .ok_or(Error::Overflow)?;
+
+            if let Some(cap) = self.cap {
+                if total_supply > cap {
+                    return Err(Error::CapExceeded);
+                }
+            }
+
+            let contract = Self::env().account_id();
+            let dead = self.dead;
+            let mut eligible_supply = self.total_supply.saturating_sub(self.balance_of(contract));
+            if dead != contract {
+                eligible_supply = eligible_supply.saturating_sub(self.balance_of(dead));
+            }
+
+            // Rounding up can promise more than `total`, in which case the
+            // accounts credited last receive less.
+            let mut shares = Vec::new();
+            let mut distributed: Balance = 0;
+            for account in self.known_accounts() {
+                if account == contract || account == dead {
+                    continue;
+                }
+
+                let share = match Self::mul_div(total, self.balance_of(account), eligible_supply) {
+                    Some((quotient, remainder)) => self
+                        .rounding_mode
+                        .round(quotient, remainder, eligible_supply)
+                        .min(total - distributed),
+                    None => 0,
+                };
+                if share > 0 {
+                    distributed += share;
+                    shares.push((account, share));
+                }
+            }
+
+            let dust = total - distributed;
+            if dust > 0 {
+                shares.push((from, dust));
+            }
+
+            if self.mint_delay > 0 {
+                for (account, share) in shares {
+                    self.issue_or_queue(from, account, share)?;
+                }
+
+                self.emit(Distribution {
+                    issuer: from,
+                    total,
+                });
+
+                return Ok(());
+            }
+
+            for (account, share) in shares {
+                self.set_balance(account, self.balance_of(account) + share);
+
+                self.emit(Transfer {
+                    from: Self::zero_account(),
+                    to: account,
+                    value: share,
+                });
+            }
+            self.set_total_supply(total_supply);
+
+            self.emit(Distribution {
+                issuer: from,
+                total,
+            });
+
+            Ok(())
+        }
+
+        pub fn transfer_issuership_help(
+            &mut self,
+            from: AccountId,
+            new_issuer: AccountId,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.issuer = new_issuer;
+
+            self.emit(IssuershipTransferred {
+                previous: from,
+                new: new_issuer,
+            });
+
+            Ok(())
+        }
+
+        /// The name is part of the domain separator, so renaming invalidates
+        /// permits signed under the old name.
+        pub fn set_name_help(&mut self, from: AccountId, name: String) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.name = Some(name);
+            self.domain_separator = self.compute_domain_separator();
+
+            self.emit_metadata_updated();
+
+            Ok(())
+        }
+
+        pub fn set_symbol_help(&mut self, from: AccountId, symbol: String) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.symbol = Some(symbol);
+
+            self.emit_metadata_updated();
+
+            Ok(())
+        }
+
+        pub fn set_treasury_help(&mut self, from: AccountId, treasury: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            let previous = self.treasury;
+            self.treasury = treasury;
+
+            self.emit(TreasuryChanged {
+                previous,
+                new: treasury,
+            });
+
+            Ok(())
+        }
+
+        pub fn set_cooldown_help(
+            &mut self,
+            from: AccountId,
+            cooldown_blocks: BlockNumber,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.cooldown_blocks = cooldown_blocks;
+
+            Ok(())
+        }
+
+        pub fn set_clawback_window_help(
+            &mut self,
+            from: AccountId,
+            clawback_window: BlockNumber,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.clawback_window = clawback_window;
+
+            Ok(())
+        }
+
+        pub fn set_min_balance_help(
+            &mut self,
+            from: AccountId,
+            min_balance: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.min_balance = min_balance;
+
+            Ok(())
+        }
+
+        pub fn set_min_transfer_help(
+            &mut self,
+            from: AccountId,
+            min_transfer: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            Self::ensure_transfer_bounds(min_transfer, self.max_transfer)?;
+            self.min_transfer = min_transfer;
+
+            Ok(())
+        }
+
+        pub fn set_max_transfer_help(
+            &mut self,
+            from: AccountId,
+            max_transfer: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            Self::ensure_transfer_bounds(self.min_transfer, max_transfer)?;
+            self.max_transfer = max_transfer;
+
+            Ok(())
+        }
+
+        /// A `max_transfer` of zero means no maximum; otherwise it must not
+        /// fall below `min_transfer`, or no amount could ever be sent.
+        fn ensure_transfer_bounds(min_transfer: Balance, max_transfer: Balance) -> Result<()> {
+            if max_transfer > 0 && min_transfer > max_transfer {
+                return Err(Error::InvalidTransferBounds);
+            }
+
+            Ok(())
+        }
+
+        pub fn set_events_enabled_help(
+            &mut self,
+            from: AccountId,
+            events_enabled: bool,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.events_enabled = events_enabled;
+
+            Ok(())
+        }
+
+        pub fn set_fee_bps_help(&mut self, from: AccountId, fee_bps: u16) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if fee_bps > MAX_FEE_BPS {
+                return Err(Error::InvalidFee);
+            }
+
+            self.fee_bps = fee_bps;
+
+            Ok(())
+        }
+
+        pub fn set_rounding_mode_help(
+            &mut self,
+            from: AccountId,
+            rounding_mode: RoundingMode,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.rounding_mode = rounding_mode;
+
+            Ok(())
+        }
+
+        pub fn set_dust_threshold_help(
+            &mut self,
+            from: AccountId,
+            dust_threshold: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.dust_threshold = dust_threshold;
+
+            Ok(())
+        }
+
+        pub fn set_inflation_help(
+            &mut self,
+            from: AccountId,
+            inflation_bps_per_period: u16,
+            period_blocks: u64,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if inflation_bps_per_period > BASIS_POINTS {
+                return Err(Error::InvalidPercent);
+            }
+
+            self.inflation_bps_per_period = inflation_bps_per_period;
+            self.period_blocks = period_blocks;
+            self.last_inflation_block = u64::from(Self::env().block_number());
+
+            Ok(())
+        }
+
+        /// Mints `total_supply * inflation_bps_per_period / 10000`, rounded
+        /// down, and moves the period forward by `period_blocks`, so missed
+        /// periods can be caught up one call at a time. Under a `mint_delay`
+        /// the mint is queued as a pending issue instead. A period that
+        /// rounds down to nothing mints nothing.
+        pub fn mint_inflation_help(&mut self) -> Result<Balance> {
+            let now = u64::from(Self::env().block_number());
+            let next = self
+                .last_inflation_block
+                .checked_add(self.period_blocks)
+                .ok_or(Error::Overflow)?;
+
+            if self.period_blocks == 0 || now < next {
+                return Err(Error::TimelockNotElapsed);
+            }
+
+            let value = Self::bps_of(
+                self.total_supply(),
+                self.inflation_bps_per_period,
+                RoundingMode::Down,
+            );
+            self.last_inflation_block = next;
+
+            if value > 0 {
+                let issuer = self.issuer;
+                self.issue_or_queue(issuer, issuer, value)?;
+            }
+
+            Ok(value)
+        }
+
+        pub fn set_allowlist_enabled_help(
+            &mut self,
+            from: AccountId,
+            allowlist_enabled: bool,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.allowlist_enabled = allowlist_enabled;
+
+            Ok(())
+        }
+
+        pub fn allow_help(&mut self, from: AccountId, account: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.allowed.insert(account, &true);
+
+            Ok(())
+        }
+
+        pub fn disallow_help(&mut self, from: AccountId, account: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.allowed.remove(account);
+
+            Ok(())
+        }
+
+        pub fn set_fee_exempt_help(
+            &mut self,
+            from: AccountId,
+            account: AccountId,
+            exempt: bool,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if exempt {
+                self.fee_exempt.insert(account, &true);
+            } else {
+                self.fee_exempt.remove(account);
+            }
+
+            Ok(())
+        }
+
+        pub fn set_spender_whitelist_enabled_help(
+            &mut self,
+            from: AccountId,
+            spender_whitelist_enabled: bool,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.spender_whitelist_enabled = spender_whitelist_enabled;
+
+            Ok(())
+        }
+
+        pub fn whitelist_spender_help(
+            &mut self,
+            from: AccountId,
+            account: AccountId,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.approved_spenders.insert(account, &true);
+
+            Ok(())
+        }
+
+        pub fn unwhitelist_spender_help(
+            &mut self,
+            from: AccountId,
+            account: AccountId,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.approved_spenders.remove(account);
+
+            Ok(())
+        }
+
+        /// Destroys the token: emits a `Burn` for the whole supply and
+        /// terminates the contract, refunding its deposit to the issuer. Every
+        /// balance is removed along with the contract storage. The `Burn` is
+        /// the last record of the supply, so `events_enabled` cannot suppress
+        /// it. Fails while tokens are held in escrow for vesting, pending
+        /// transfers or streams, which would go without a trace.
+        pub fn terminate_help(&mut self, from: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if self.escrowed > 0 {
+                return Err(Error::EscrowNotEmpty);
+            }
+
+            Self::env().emit_event(Burn {
+                from,
+                value: self.total_supply,
+            });
+
+            Self::env().terminate_contract(from)
+        }
+
+        pub fn set_strict_approve_help(
+            &mut self,
+            from: AccountId,
+            strict_approve: bool,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.strict_approve = strict_approve;
+
+            Ok(())
+        }
+
+        pub fn set_unit_size_help(&mut self, from: AccountId, unit_size: Balance) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.unit_size = unit_size;
+
+            Ok(())
+        }
+
+        /// Hands the issuer role to the zero account, which nobody can sign
+        /// for. Multi-signature issuers, minters, queued issues, airdrops and
+        /// the inflation schedule are dropped as well, so the supply can never
+        /// grow again.
+        pub fn renounce_issuership_help(&mut self, from: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.issuer = Self::zero_account();
+            self.issuers = Vec::new();
+            for account in self.minters() {
+                self.minter_positions.remove(account);
+            }
+            for position in 0..self.minter_count {
+                self.minters.remove(position);
+            }
+            self.minter_count = 0;
+            for id in 0..self.pending_issue_count {
+                self.pending_issues.remove(id);
+            }
+            for id in 1..=self.snapshot_id {
+                self.airdrops.remove(id);
+            }
+            self.inflation_bps_per_period = 0;
+            self.period_blocks = 0;
+
+            self.emit(IssuershipRenounced { previous: from });
+
+            Ok(())
+        }
+
+        pub fn pause_help(&mut self, from: AccountId) -> Result<()> {
+            self.ensure_issuer_action(from)?;
+
+            self.set_paused(from, true);
+
+            Ok(())
+        }
+
+        pub fn unpause_help(&mut self, from: AccountId) -> Result<()> {
+            self.ensure_issuer_action(from)?;
+
+            self.set_paused(from, false);
+
+            Ok(())
+        }
+
+        fn set_paused(&mut self, issuer: AccountId, paused: bool) {
+            self.paused = paused;
+
+            if paused {
+                self.emit(Paused { issuer });
+            } else {
+                self.emit(Unpaused { issuer });
+            }
+        }
+
+        fn is_issuer(&self, account: AccountId) -> bool {
+            if self.issuers.is_empty() {
+                account == self.issuer
+            } else {
+                self.issuers.contains(&account)
+            }
+        }
+
+        /// Issuing and pausing can only be called directly when a single
+        /// confirmation suffices; otherwise they have to go through `propose_*`.
+        fn ensure_issuer_action(&self, from: AccountId) -> Result<()> {
+            if !self.is_issuer(from) {
+                return Err(Error::NotIssuer);
+            }
+
+            if self.threshold > 1 {
+                return Err(Error::ThresholdNotMet);
+            }
+
+            Ok(())
+        }
+
+        pub fn propose_help(&mut self, from: AccountId, action: Action) -> Result<u32> {
+            if !self.is_issuer(from) {
+                return Err(Error::NotIssuer);
+            }
+
+            let id = self.proposal_count;
+            self.proposal_count = id.checked_add(1).ok_or(Error::Overflow)?;
+            self.proposals.insert(
+                id,
+                &Proposal {
+                    action: action.clone(),
+                    proposer: from,
+                    confirmations: Vec::new(),
+                },
+            );
+
+            self.emit(Proposed {
+                id,
+                proposer: from,
+                action,
+            });
+
+            self.confirm_help(from, id)?;
+
+            Ok(id)
+        }
+
+        /// Records `from`'s confirmation and executes the proposal once it
+        /// reaches `threshold` confirmations.
+        pub fn confirm_help(&mut self, from: AccountId, proposal_id: u32) -> Result<()> {
+            if !self.is_issuer(from) {
+                return Err(Error::NotIssuer);
+            }
+
+            let mut proposal = self
+                .proposals
+                .get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            if proposal.confirmations.contains(&from) {
+                return Err(Error::AlreadyConfirmed);
+            }
+
+            proposal.confirmations.push(from);
+
+            self.emit(Confirmed {
+                id: proposal_id,
+                issuer: from,
+            });
+
+            if (proposal.confirmations.len() as u32) < self.threshold {
+                self.proposals.insert(proposal_id, &proposal);
+                return Ok(());
+            }
+
+            match proposal.action {
+                Action::Issue(value) => {
+                    self.issue_or_queue(proposal.proposer, proposal.proposer, value)?
+                }
+                Action::Pause => self.set_paused(proposal.proposer, true),
+                Action::Unpause => self.set_paused(proposal.proposer, false),
+            }
+            self.proposals.remove(proposal_id);
+
+            Ok(())
+        }
+
+        pub fn freeze_help(&mut self, from: AccountId, account: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.frozen.insert(account, &true);
+
+            self.emit(Frozen { account });
+
+            Ok(())
+        }
+
+        pub fn unfreeze_help(&mut self, from: AccountId, account: AccountId) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            self.frozen.remove(account);
+
+            self.emit(Unfrozen { account });
+
+            Ok(())
+        }
+
+        /// Adds `amount` to the locked part of the balance of `account`. The
+        /// lock may exceed the current balance, in which case tokens received
+        /// later stay locked too.
+        pub fn lock_help(
+            &mut self,
+            from: AccountId,
+            account: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            let locked = self
+                .locked_of(account)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.locked.insert(account, &locked);
+
+            Ok(())
+        }
+
+        pub fn unlock_help(
+            &mut self,
+            from: AccountId,
+            account: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            let locked = self
+                .locked_of(account)
+                .checked_sub(amount)
+                .ok_or(Error::Overflow)?;
+            if locked == 0 {
+                self.locked.remove(account);
+            } else {
+                self.locked.insert(account, &locked);
+            }
+
+            Ok(())
+        }
+
+        /// The part of the balance of `account` the issuer may move by force.
+        /// Escrow held in the contract's own account is off limits.
+        fn forcible_balance(&self, account: AccountId) -> Balance {
+            let balance = self.balance_of(account);
+            if account == Self::env().account_id() {
+                return balance.saturating_sub(self.escrowed);
+            }
+
+            balance
+        }
+
+        /// Moves tokens out of `from` without its consent. Allowances, pausing
+        /// and freezing are all ignored, since the accounts this is meant for
+        /// are usually frozen already. Escrow is left alone.
+        pub fn force_transfer_help(
+            &mut self,
+            issuer: AccountId,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if issuer != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            Self::ensure_not_zero(to)?;
+
+            if self.forcible_balance(from) < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.move_balance(from, to, value)?;
+
+            self.emit(ForceTransfer {
+                issuer,
+                from,
+                to,
+                value,
+            });
+            self.emit(Transfer { from, to, value });
+
+            Ok(())
+        }
+
+        /// Sweeps the whole balance of `from` into `into`. Like `force_transfer`
+        /// this ignores pausing and freezing. Escrow held in the contract's own
+        /// account is left in place.
+        pub fn merge_accounts_help(
+            &mut self,
+            issuer: AccountId,
+            from: AccountId,
+            into: AccountId,
+        ) -> Result<()> {
+            if issuer != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            Self::ensure_not_zero(into)?;
+
+            if from == into {
+                return Ok(());
+            }
+
+            let value = self.forcible_balance(from);
+            if value == 0 {
+                return Ok(());
+            }
+
+            self.move_balance(from, into, value)?;
+
+            self.emit(Transfer {
+                from,
+                to: into,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Repairs a balance corrupted by the old `approve`, which moved tokens
+        /// instead of granting an allowance. Sets the balance of `owner` to
+        /// `correct_balance` and moves `total_supply` by the same amount, which
+        /// shows up as a mint or burn in `Transfer` events. The contract's own
+        /// account cannot be set below the escrow it holds.
+        pub fn reconcile_help(
+            &mut self,
+            from: AccountId,
+            owner: AccountId,
+            correct_balance: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if owner == Self::env().account_id() && correct_balance < self.escrowed {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let old = self.balance_of(owner);
+            let (total_supply, transfer) = if correct_balance >= old {
+                let delta = correct_balance - old;
+                let total_supply = self
+                    .total_supply
+                    .checked_add(delta)
+                    .ok_or(Error::Overflow)?;
+
+                if let Some(cap) = self.cap {
+                    if total_supply > cap {
+                        return Err(Error::CapExceeded);
+                    }
+                }
+
+                (
+                    total_supply,
+                    Transfer {
+                        from: Self::zero_account(),
+                        to: owner,
+                        value: delta,
+                    },
+                )
+            } else {
+                let delta = old - correct_balance;
+                let total_supply = self
+                    .total_supply
+                    .checked_sub(delta)
+                    .ok_or(Error::Overflow)?;
+
+                (
+                    total_supply,
+                    Transfer {
+                        from: owner,
+                        to: Self::zero_account(),
+                        value: delta,
+                    },
+                )
+            };
+
+            self.set_balance(owner, correct_balance);
+            self.set_total_supply(total_supply);
+
+            self.emit(transfer);
+            self.emit(Adjustment {
+                owner,
+                old,
+                new: correct_balance,
+            });
+
+            Ok(())
+        }
+
+        /// Moves every balance to the recovery account `to`. Only allowed while
+        /// paused, so the token has to be stopped before it can be drained.
+        /// Escrow in the contract's own account stays put, so vesting, pending
+        /// transfers and streams can still settle.
+        pub fn emergency_withdraw_all_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if !self.paused {
+                return Err(Error::NotPaused);
+            }
+
+            let escrow = Self::env().account_id();
+            let holders: Vec<AccountId> = self
+                .known_accounts()
+                .filter(|account| *account != to && *account != escrow)
+                .collect();
+            for holder in holders {
+                let value = self.balance_of(holder);
+                if value == 0 {
+                    continue;
+                }
+
+                self.move_balance(holder, to, value)?;
+
+                self.emit(Transfer {
+                    from: holder,
+                    to,
+                    value,
+                });
+            }
+
+            self.emit(EmergencyDrain { to });
+
+            Ok(())
+        }
+
+        /// Returns tokens that were sent to the contract's own account by
+        /// mistake. Escrow held there is not rescuable.
+        pub fn rescue_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            let contract = Self::env().account_id();
+            let rescuable = self.balance_of(contract).saturating_sub(self.escrowed);
+            if rescuable < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.move_balance(contract, to, value)?;
+
+            self.emit(Transfer {
+                from: contract,
+                to,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Reverses the most recent transfer by `original_sender`, as long as it
+        /// happened no more than `clawback_window` blocks ago. Like
+        /// `force_transfer`, this ignores pausing and freezing and leaves
+        /// escrow alone. A transfer can only be clawed back once.
+        pub fn clawback_help(
+            &mut self,
+            issuer: AccountId,
+            original_sender: AccountId,
+        ) -> Result<()> {
+            if issuer != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            let (to, value, at) = self
+                .clawbackable
+                .get(original_sender)
+                .ok_or(Error::ClawbackWindowClosed)?;
+
+            if Self::env().block_number() - at > self.clawback_window {
+                return Err(Error::ClawbackWindowClosed);
+            }
+
+            if self.forcible_balance(to) < value {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.move_balance(to, original_sender, value)?;
+            self.clawbackable.remove(original_sender);
+
+            self.emit(Clawback {
+                issuer,
+                from: to,
+                to: original_sender,
+                value,
+            });
+            self.emit(Transfer {
+                from: to,
+                to: original_sender,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Moves `value` from `from` into the contract's own account and sets it
+        /// aside from what `rescue` may touch.
+        fn lock_in_escrow(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let escrow = Self::env().account_id();
+            let escrowed = self.escrowed.checked_add(value).ok_or(Error::Overflow)?;
+            self.move_balance(from, escrow, value)?;
+            self.escrowed = escrowed;
+
+            self.emit(Transfer {
+                from,
+                to: escrow,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Pays `value` of escrowed tokens out to `to`.
+        fn release_from_escrow(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            if value == 0 {
+                return Ok(());
+            }
+
+            let escrow = Self::env().account_id();
+            let escrowed = self.escrowed.checked_sub(value).ok_or(Error::Overflow)?;
+            self.move_balance(escrow, to, value)?;
+            self.escrowed = escrowed;
+
+            self.emit(Transfer {
+                from: escrow,
+                to,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Moves `total` from the issuer into escrow, releasing it linearly to
+        /// `beneficiary` over the next `duration` blocks. A beneficiary has at
+        /// most one schedule at a time.
+        pub fn create_vesting_help(
+            &mut self,
+            from: AccountId,
+            beneficiary: AccountId,
+            total: Balance,
+            duration: BlockNumber,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if self.vesting.get(beneficiary).is_some() {
+                return Err(Error::VestingExists);
+            }
+
+            let escrow = Self::env().account_id();
+            self.ensure_transferable(from, escrow)?;
+
+            if self.balance_of(from) < total {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.lock_in_escrow(from, total)?;
+
+            let start = Self::env().block_number();
+            self.vesting.insert(beneficiary, &(total, start, duration));
+
+            self.emit(VestingCreated {
+                beneficiary,
+                total,
+                start,
+                duration,
+            });
+
+            Ok(())
+        }
+
+        /// Pays out whatever has unlocked since the last claim. The schedule is
+        /// removed once it has been claimed in full.
+        pub fn claim_vested_help(&mut self, beneficiary: AccountId) -> Result<()> {
+            let (total, start, duration) = self
+                .vesting
+                .get(beneficiary)
+                .ok_or(Error::VestingNotFound)?;
+
+            let elapsed = Self::env().block_number().saturating_sub(start);
+            let unlocked = if elapsed >= duration {
+                total
+            } else {
+                total
+                    .checked_mul(Balance::from(elapsed))
+                    .ok_or(Error::Overflow)?
+                    / Balance::from(duration)
+            };
+            let claimed = self.vesting_claimed.get(beneficiary).unwrap_or_default();
+            let value = unlocked - claimed;
+
+            let escrow = Self::env().account_id();
+            self.ensure_transferable(escrow, beneficiary)?;
+
+            self.release_from_escrow(beneficiary, value)?;
+
+            if unlocked == total {
+                self.vesting.remove(beneficiary);
+                self.vesting_claimed.remove(beneficiary);
+            } else {
+                self.vesting_claimed.insert(beneficiary, &unlocked);
+            }
+
+            self.emit(VestingClaimed { beneficiary, value });
+
+            Ok(())
+        }
+
+        /// Escrows `rate_per_block * duration` from `from`, accruing to `to`
+        /// block by block from now on. Returns the id of the stream.
+        pub fn create_stream_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            rate_per_block: Balance,
+            duration: BlockNumber,
+        ) -> Result<u32> {
+            let total = rate_per_block
+                .checked_mul(Balance::from(duration))
+                .ok_or(Error::Overflow)?;
+
+            let escrow = Self::env().account_id();
+            self.ensure_transferable(from, escrow)?;
+            self.check_transfer(from, to, total)?;
+
+            let id = self.stream_count;
+            let stream_count = id.checked_add(1).ok_or(Error::Overflow)?;
+            self.lock_in_escrow(from, total)?;
+            self.record_transfer(from);
+            self.stream_count = stream_count;
+            self.streams.insert(
+                id,
+                &Stream {
+                    sender: from,
+                    recipient: to,
+                    rate_per_block,
+                    start: Self::env().block_number(),
+                    duration,
+                    withdrawn: 0,
+                },
+            );
+
+            self.emit(StreamCreated {
+                id,
+                sender: from,
+                recipient: to,
+                rate_per_block,
+                duration,
+            });
+
+            Ok(id)
+        }
+
+        /// Pays the recipient of `stream_id` everything accrued since its last
+        /// withdrawal and returns that amount. The stream is removed once it
+        /// has paid out in full.
+        pub fn withdraw_stream_help(&mut self, from: AccountId, stream_id: u32) -> Result<Balance> {
+            let mut stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+
+            if from != stream.recipient {
+                return Err(Error::NotStreamParty);
+            }
+
+            let escrow = Self::env().account_id();
+            self.ensure_transferable(escrow, from)?;
+
+            let accrued = stream.accrued(Self::env().block_number());
+            let value = accrued - stream.withdrawn;
+            self.release_from_escrow(from, value)?;
+
+            if accrued == stream.total() {
+                self.streams.remove(stream_id);
+            } else {
+                stream.withdrawn = accrued;
+                self.streams.insert(stream_id, &stream);
+            }
+
+            self.emit(StreamWithdrawn {
+                id: stream_id,
+                recipient: from,
+                value,
+            });
+
+            Ok(value)
+        }
+
+        /// Ends `stream_id` early: the recipient is paid what has accrued and
+        /// the sender gets the rest back. Either side may cancel.
+        pub fn cancel_stream_help(&mut self, from: AccountId, stream_id: u32) -> Result<()> {
+            let stream = self.streams.get(stream_id).ok_or(Error::StreamNotFound)?;
+
+            if from != stream.sender && from != stream.recipient {
+                return Err(Error::NotStreamParty);
+            }
+
+            let escrow = Self::env().account_id();
+            self.ensure_transferable(escrow, stream.recipient)?;
+            self.ensure_transferable(escrow, stream.sender)?;
+
+            let accrued = stream.accrued(Self::env().block_number());
+            let paid = accrued - stream.withdrawn;
+            let refunded = stream.total() - accrued;
+            self.release_from_escrow(stream.recipient, paid)?;
+            self.release_from_escrow(stream.sender, refunded)?;
+            self.streams.remove(stream_id);
+
+            self.emit(StreamCancelled {
+                id: stream_id,
+                paid,
+                refunded,
+            });
+
+            Ok(())
+        }
+
+        /// First half of a two-step transfer: escrows `value` until `to`
+        /// claims it or `from` cancels. Repeated transfers to the same
+        /// recipient add up.
+        pub fn transfer_pending_help(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.check_transfer(from, to, value)?;
+
+            let pending = self
+                .pending_transfer(from, to)
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            self.lock_in_escrow(from, value)?;
+            self.record_transfer(from);
+            self.pending.insert((from, to), &pending);
+
+            self.emit(TransferPending { from, to, value });
+
+            Ok(())
+        }
+
+        pub fn claim_pending_help(&mut self, from: AccountId, to: AccountId) -> Result<()> {
+            self.ensure_transferable(from, to)?;
+
+            let value = self.pending.get((from, to)).ok_or(Error::PendingNotFound)?;
+            self.release_from_escrow(to, value)?;
+            self.pending.remove((from, to));
+
+            self.emit(TransferClaimed { from, to, value });
+
+            Ok(())
+        }
+
+        /// Returns an unclaimed pending transfer to its sender.
+        pub fn cancel_pending_help(&mut self, from: AccountId, to: AccountId) -> Result<()> {
+            let value = self.pending.get((from, to)).ok_or(Error::PendingNotFound)?;
+            self.release_from_escrow(from, value)?;
+            self.pending.remove((from, to));
+
+            self.emit(TransferCancelled { from, to, value });
+
+            Ok(())
+        }
+
+        pub fn snapshot_help(&mut self, from: AccountId) -> Result<u32> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            let id = self.snapshot_id.checked_add(1).ok_or(Error::Overflow)?;
+            self.snapshot_id = id;
+
+            self.emit(Snapshot { id });
+
+            Ok(id)
+        }
+
+        /// Lets every holder at `snapshot_id` claim `per_token_bps` basis
+        /// points of its balance there in newly issued tokens, at most
+        /// `BASIS_POINTS`. Announcing again replaces the rate for claims still
+        /// to come.
+        pub fn announce_airdrop_help(
+            &mut self,
+            from: AccountId,
+            snapshot_id: u32,
+            per_token_bps: u16,
+        ) -> Result<()> {
+            if from != self.issuer {
+                return Err(Error::NotIssuer);
+            }
+
+            if snapshot_id == 0 || snapshot_id > self.snapshot_id {
+                return Err(Error::InvalidSnapshot);
+            }
+
+            if per_token_bps > BASIS_POINTS {
+                return Err(Error::InvalidPercent);
+            }
+
+            self.airdrops.insert(snapshot_id, &per_token_bps);
+
+            self.emit(AirdropAnnounced {
+                snapshot_id,
+                per_token_bps,
+            });
+
+            Ok(())
+        }
+
+        /// Issues the airdrop of `snapshot_id` owed to `from`, rounded down,
+        /// and returns it. `per_token_bps` must repeat the announced rate, so a
+        /// claim never goes through on terms the claimer did not see. Each
+        /// account claims once per snapshot. Under a `mint_delay` the claim is
+        /// queued as a pending issue instead.
+        pub fn claim_airdrop_help(
+            &mut self,
+            from: AccountId,
+            snapshot_id: u32,
+            per_token_bps: u16,
+        ) -> Result<Balance> {
+            let rate = self
+                .airdrops
+                .get(snapshot_id)
+                .ok_or(Error::AirdropNotFound)?;
+
+            if rate != per_token_bps {
+                return Err(Error::InvalidPercent);
+            }
+
+            if self.has_claimed_airdrop(snapshot_id, from) {
+                return Err(Error::AlreadyClaimed);
+            }
+
+            let value = Self::bps_of(
+                self.balance_of_at(from, snapshot_id)?,
+                rate,
+                RoundingMode::Down,
+            );
+            if value > 0 {
+                let issuer = self.issuer;
+                self.issue_or_queue(issuer, from, value)?;
+            }
+            self.airdrop_claimed.insert((snapshot_id, from), &true);
+
+            self.emit(AirdropClaimed {
+                account: from,
+                snapshot_id,
+                value,
+            });
+
+            Ok(value)
+        }
+    }
+
+    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
+    /// module and test functions are marked with a `#[test]` attribute.
+    /// The below code is technically just normal Rust code.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ink_lang as ink;
+
+        use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
+
+        type Event = <Erc20 as ink::reflect::ContractEventBase>::Type;
+
+        #[cfg(feature = "hooks")]
+        std::thread_local! {
+            static HOOK_CALLS: core::cell::Cell<(u32, u32)> = const { core::cell::Cell::new((0, 0)) };
+        }
+
+        /// Counts hook invocations so tests can check they fire.
+        #[cfg(feature = "hooks")]
+        #[derive(Default)]
+        pub struct CountingHook;
+
+        #[cfg(feature = "hooks")]
+        impl TransferHook for CountingHook {
+            fn before_transfer(&self, _from: AccountId, _to: AccountId, _value: Balance) {
+                HOOK_CALLS.with(|calls| {
+                    let (before, after) = calls.get();
+                    calls.set((before + 1, after));
+                });
+            }
+
+            fn after_transfer(&self, _from: AccountId, _to: AccountId, _value: Balance) {
+                HOOK_CALLS.with(|calls| {
+                    let (before, after) = calls.get();
+                    calls.set((before, after + 1));
+                });
+            }
+        }
+
+        /// A call the token made to a contract registered with
+        /// `mock_contract`.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum Callback {
+            OnTokenTransfer(AccountId, Balance, Vec<u8>),
+            ReceiveApproval(AccountId, Balance, Vec<u8>),
+            OnErc20Received(AccountId, Balance),
+        }
+
+        type Handler = std::rc::Rc<dyn Fn() -> bool>;
+
+        std::thread_local! {
+            static MOCK_CONTRACTS: core::cell::RefCell<Vec<(AccountId, Handler)>> =
+                const { core::cell::RefCell::new(Vec::new()) };
+            static MOCK_CALLS: core::cell::RefCell<Vec<(AccountId, Callback)>> =
+                const { core::cell::RefCell::new(Vec::new()) };
+        }
+
+        /// Stands in for the contracts pallet. Accounts registered with
+        /// `mock_contract` are contracts answering every callback with their
+        /// handler; any other account is a plain one.
+        #[derive(Default)]
+        pub struct MockCallee;
+
+        impl MockCallee {
+            fn call(&self, callee: AccountId, callback: Callback) -> bool {
+                MOCK_CALLS.with(|calls| calls.borrow_mut().push((callee, callback)));
+                let handler = MOCK_CONTRACTS.with(|contracts| {
+                    contracts
+                        .borrow()
+                        .iter()
+                        .find(|(account, _)| *account == callee)
+                        .map(|(_, handler)| handler.clone())
+                });
+
+                handler.is_some_and(|handler| handler())
+            }
+        }
+
+        impl Callee for MockCallee {
+            fn is_contract(&self, account: AccountId) -> bool {
+                MOCK_CONTRACTS.with(|contracts| {
+                    contracts
+                        .borrow()
+                        .iter()
+                        .any(|(contract, _)| *contract == account)
+                })
+            }
+
+            fn on_token_transfer(
+                &self,
+                to: AccountId,
+                from: AccountId,
+                value: Balance,
+                data: Vec<u8>,
+            ) -> bool {
+                self.call(to, Callback::OnTokenTransfer(from, value, data))
+            }
+
+            fn receive_approval(
+                &self,
+                spender: AccountId,
+                owner: AccountId,
+                value: Balance,
+                data: Vec<u8>,
+            ) -> bool {
+                self.call(spender, Callback::ReceiveApproval(owner, value, data))
+            }
+
+            fn on_erc20_received(
+                &self,
+                to: AccountId,
+                from: AccountId,
+                value: Balance,
+            ) -> Option<[u8; 4]> {
+                if self.call(to, Callback::OnErc20Received(from, value)) {
+                    Some(ERC20_RECEIVED)
+                } else {
+                    None
+                }
+            }
+        }
+
+        /// Turns `account` into a contract whose callbacks succeed when
+        /// `handler` returns true.
+        fn mock_contract(account: AccountId, handler: impl Fn() -> bool + 'static) {
+            MOCK_CONTRACTS.with(|contracts| {
+                contracts
+                    .borrow_mut()
+                    .push((account, std::rc::Rc::new(handler)))
+            });
+        }
+
+        fn mock_calls() -> Vec<(AccountId, Callback)> {
+            MOCK_CALLS.with(|calls| calls.borrow().clone())
+        }
+
+        /// Runs `message` on the token as stored, the way a message re-entering
+        /// it from a callback would. The result is stored unless the message
+        /// fails, since the dispatcher reverts failed messages.
+        fn reenter<T>(message: impl FnOnce(&mut Erc20) -> Result<T>) -> Result<T> {
+            let mut contract: Erc20 = ink_storage::traits::pull_spread_root(&ROOT_KEY);
+            let result = message(&mut contract);
+            if result.is_ok() {
+                push_spread_root(&contract, &ROOT_KEY);
+            }
+
+            result
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(caller);
+        }
+
+        fn keypair(seed: u8) -> (SecretKey, AccountId) {
+            let secret = SecretKey::from_slice(&[seed; 32]).expect("Invalid secret key");
+            let public = PublicKey::from_secret_key(SECP256K1, &secret).serialize();
+            let mut account = [0x0; 32];
+            ink_env::hash_bytes::<Blake2x256>(&public, &mut account);
+
+            (secret, account.into())
+        }
+
+        fn sign(secret: &SecretKey, message_hash: [u8; 32]) -> [u8; 65] {
+            let message = Message::from_slice(&message_hash).expect("Invalid message hash");
+            let (recovery_id, compact) = SECP256K1
+                .sign_ecdsa_recoverable(&message, secret)
+                .serialize_compact();
+
+            let mut signature = [0x0; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            signature
+        }
+
+        fn recorded_event(index: usize) -> Event {
+            let event = ink_env::test::recorded_events()
+                .nth(index)
+                .expect("Event not recorded");
+
+            <Event as scale::Decode>::decode(&mut &event.data[..]).expect("Invalid event data")
+        }
+
+        #[ink::test]
+        fn create_contract_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn create_contract_with_metadata_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new_with_metadata(
+                1000,
+                Some(String::from("Token")),
+                Some(String::from("TKN")),
+                12,
+            );
+
+            assert_eq!(erc20.token_name(), Some(String::from("Token")));
+            assert_eq!(erc20.token_symbol(), Some(String::from("TKN")));
+            assert_eq!(erc20.token_decimals(), 12);
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        fn create_contract_without_metadata_uses_defaults() {
+            let erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.token_name(), None);
+            assert_eq!(erc20.token_symbol(), None);
+            assert_eq!(erc20.token_decimals(), 18);
+        }
+
+        #[ink::test]
+        fn stored_contract_keeps_balances() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_metadata(100, Some(String::from("Token")), None, 0);
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+
+            // What the dispatcher stores after a message and loads for the
+            // next one.
+            push_spread_root(&erc20, &ROOT_KEY);
+            let stored: Erc20 = pull_spread_root(&ROOT_KEY);
+
+            assert_eq!(stored.token_name(), Some(String::from("Token")));
+            assert_eq!(stored.balance_of(accounts.alice), 90);
+            assert_eq!(stored.balance_of(accounts.bob), 10);
+            assert_eq!(stored.allowance(accounts.alice, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 900);
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn transfer_entire_balance_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.transfer(accounts.bob, 1000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 0);
+            assert_eq!(erc20.balance_of(accounts.bob), 1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.alice, 400), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 400);
+            assert_eq!(erc20.balance_of(accounts.bob), 600);
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn transfer_zero_value_emits_no_event() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.transfer(accounts.bob, 0), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn transfer_to_self_keeps_balance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.transfer(accounts.alice, 400), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(
+                erc20.transfer(accounts.alice, 1001),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn transfer_from_to_owner_keeps_balance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.alice, 0),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.alice, 60),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 40);
+        }
+
+        #[ink::test]
+        fn transfer_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            assert_eq!(
+                erc20.transfer(accounts.bob, 2000),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        fn approve_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+
+            set_caller(accounts.bob);
+
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 50),
+                Ok(())
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 950);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 50);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn approve_failed_with_insufficientallowance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+
+            set_caller(accounts.bob);
+
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 500),
+                Err(Error::InsufficientAllowance)
+            );
+        }
+
+        #[ink::test]
+        fn increase_allowance_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.increase_allowance(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+
+            assert_eq!(erc20.increase_allowance(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 150);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.decrease_allowance(accounts.bob, 40), Ok(()));
+
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 60);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_failed_with_insufficientallowance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(
+                erc20.decrease_allowance(accounts.bob, 101),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn transfer_from_debits_owner() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 100),
+                Ok(())
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 900);
+            assert_eq!(erc20.balance_of(accounts.charlie), 100);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn transfer_from_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 2000), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 1500),
+                Err(Error::InsufficientBalance)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 2000);
+        }
+
+        #[ink::test]
+        fn burn_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            assert_eq!(erc20.burn(100), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 900);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn burn_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            assert_eq!(erc20.burn(2000), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn burn_from_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn_from(accounts.alice, 60), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 940);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 40);
+            assert_eq!(erc20.total_supply(), 940);
+            assert_eq!(ink_env::test::recorded_events().count(), 5);
+        }
+
+        #[ink::test]
+        fn burn_from_failed_with_insufficientallowance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.burn_from(accounts.alice, 101),
+                Err(Error::InsufficientAllowance)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn burn_from_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 2000), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.burn_from(accounts.alice, 1500),
+                Err(Error::InsufficientBalance)
+            );
+
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 2000);
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn issue_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            assert_eq!(erc20.issue(1000), Ok(()));
+
+            assert_eq!(erc20.total_supply(), 2000);
+            assert_eq!(erc20.balance_of(accounts.alice), 2000);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn issue_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            set_caller(accounts.bob);
+
+            assert_eq!(erc20.issue(1000), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn issue_up_to_max_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(Balance::MAX - 10);
+
+            assert_eq!(erc20.issue(10), Ok(()));
+
+            assert_eq!(erc20.total_supply(), Balance::MAX);
+            assert_eq!(erc20.balance_of(accounts.alice), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn issue_failed_with_overflow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(Balance::MAX - 10);
+
+            assert_eq!(erc20.issue(11), Err(Error::Overflow));
+
+            assert_eq!(erc20.total_supply(), Balance::MAX - 10);
+            assert_eq!(erc20.balance_of(accounts.alice), Balance::MAX - 10);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn transfer_issuership_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.transfer_issuership(accounts.bob), Ok(()));
+            assert_eq!(erc20.issue(100), Err(Error::NotIssuer));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue(100), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.total_supply(), 1100);
+            assert_eq!(ink_env::test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn transfer_issuership_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_issuership(accounts.bob),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.issue(100), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn pause_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.pause(), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.bob, 100), Err(Error::Paused));
+            assert_eq!(erc20.approve(accounts.bob, 100), Err(Error::Paused));
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 100),
+                Err(Error::Paused)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.total_supply(), 1000);
+
+            assert_eq!(erc20.unpause(), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(ink_env::test::recorded_events().count(), 5);
+        }
+
+        #[ink::test]
+        fn pause_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.pause(), Err(Error::NotIssuer));
+            assert_eq!(erc20.unpause(), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn issue_up_to_cap_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_capped(1000, 2000);
+
+            assert_eq!(erc20.cap(), Some(2000));
+            assert_eq!(erc20.issue(1000), Ok(()));
+
+            assert_eq!(erc20.total_supply(), 2000);
+            assert_eq!(erc20.balance_of(accounts.alice), 2000);
+        }
+
+        #[ink::test]
+        fn issue_failed_with_capexceeded() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_capped(1000, 2000);
+
+            assert_eq!(erc20.issue(1001), Err(Error::CapExceeded));
+
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "total supply exceeds cap")]
+        fn create_capped_contract_above_cap_fails() {
+            Erc20::new_capped(2001, 2000);
+        }
+
+        #[ink::test]
+        fn create_contract_is_uncapped() {
+            let erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.cap(), None);
+        }
+
+        #[ink::test]
+        fn transfer_batch_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(
+                erc20.transfer_batch(vec![
+                    (accounts.bob, 100),
+                    (accounts.charlie, 200),
+                    (accounts.django, 300),
+                ]),
+                Ok(())
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 400);
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.charlie), 200);
+            assert_eq!(erc20.balance_of(accounts.django), 300);
+            assert_eq!(ink_env::test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn transfer_batch_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(
+                erc20.transfer_batch(vec![
+                    (accounts.bob, 400),
+                    (accounts.charlie, 400),
+                    (accounts.django, 400),
+                ]),
+                Err(Error::InsufficientBalance)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.balance_of(accounts.django), 0);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn transfer_batch_failed_with_overflow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, Balance::MAX), (accounts.charlie, 1)]),
+                Err(Error::Overflow)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        fn issue_emits_transfer_from_zero() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.issue(100), Ok(()));
+
+            match recorded_event(1) {
+                Event::Issue(Issue { issuer, to, value }) => {
+                    assert_eq!(issuer, accounts.alice);
+                    assert_eq!(to, accounts.alice);
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected an Issue event"),
+            }
+            match recorded_event(2) {
+                Event::Transfer(Transfer { from, to, value }) => {
+                    assert_eq!(from, AccountId::from([0x0; 32]));
+                    assert_eq!(to, accounts.alice);
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected a Transfer event"),
+            }
+        }
+
+        #[ink::test]
+        fn issue_to_emits_issuer_and_recipient() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.add_minter(accounts.bob), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue_to(accounts.charlie, 100), Ok(()));
+            match recorded_event(ink_env::test::recorded_events().count() - 2) {
+                Event::Issue(Issue { issuer, to, value }) => {
+                    assert_eq!(issuer, accounts.bob);
+                    assert_eq!(to, accounts.charlie);
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected an Issue event"),
+            }
+        }
+
+        #[ink::test]
+        fn execute_issue_emits_queued_issuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 1);
+            assert_eq!(erc20.issue_to(accounts.charlie, 100), Ok(()));
+            match recorded_event(1) {
+                Event::IssueQueued(IssueQueued { issuer, to, .. }) => {
+                    assert_eq!(issuer, accounts.alice);
+                    assert_eq!(to, accounts.charlie);
+                }
+                _ => panic!("Expected an IssueQueued event"),
+            }
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            set_caller(accounts.bob);
+            assert_eq!(erc20.execute_issue(0), Ok(()));
+            match recorded_event(2) {
+                Event::Issue(Issue { issuer, to, value }) => {
+                    assert_eq!(issuer, accounts.alice);
+                    assert_eq!(to, accounts.charlie);
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected an Issue event"),
+            }
+        }
+
+        #[ink::test]
+        fn burn_emits_transfer_to_zero() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.burn(100), Ok(()));
+
+            match recorded_event(1) {
+                Event::Burn(Burn { from, value }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected a Burn event"),
+            }
+            match recorded_event(2) {
+                Event::Transfer(Transfer { from, to, value }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(to, AccountId::from([0x0; 32]));
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected a Transfer event"),
+            }
+        }
+
+        #[ink::test]
+        fn freeze_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.transfer(accounts.bob, 500), Ok(()));
+            assert_eq!(erc20.freeze(accounts.bob), Ok(()));
+            assert!(erc20.is_frozen(accounts.bob));
+
+            assert_eq!(erc20.transfer(accounts.bob, 100), Err(Error::AccountFrozen));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer(accounts.charlie, 100),
+                Err(Error::AccountFrozen)
+            );
+            assert_eq!(
+                erc20.approve(accounts.charlie, 100),
+                Err(Error::AccountFrozen)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 500);
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.unfreeze(accounts.bob), Ok(()));
+            assert!(!erc20.is_frozen(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 100), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.charlie), 100);
+        }
+
+        #[ink::test]
+        fn freeze_blocks_transfer_from() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.freeze(accounts.charlie), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 50),
+                Err(Error::AccountFrozen)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn freeze_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.freeze(accounts.alice), Err(Error::NotIssuer));
+            assert_eq!(erc20.unfreeze(accounts.alice), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn transfer_batch_failed_with_frozen_recipient() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.freeze(accounts.charlie), Ok(()));
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 100), (accounts.charlie, 100)]),
+                Err(Error::AccountFrozen)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn error_encoding_is_stable() {
+            assert_eq!(scale::Encode::encode(&Error::InsufficientBalance), [0x00]);
+            assert_eq!(scale::Encode::encode(&Error::InsufficientAllowance), [0x01]);
+            assert_eq!(scale::Encode::encode(&Error::NotIssuer), [0x02]);
+        }
+
+        #[ink::test]
+        fn holder_count_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.holder_count(), 1);
+
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.holder_count(), 2);
+
+            assert_eq!(erc20.transfer(accounts.bob, 0), Ok(()));
+            assert_eq!(erc20.transfer(accounts.alice, 100), Ok(()));
+            assert_eq!(erc20.holder_count(), 2);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn(40), Ok(()));
+            assert_eq!(erc20.transfer(accounts.alice, 60), Ok(()));
+            assert_eq!(erc20.holder_count(), 1);
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.burn(960), Ok(()));
+            assert_eq!(erc20.holder_count(), 0);
+
+            assert_eq!(erc20.issue(10), Ok(()));
+            assert_eq!(erc20.holder_count(), 1);
+        }
+
+        #[ink::test]
+        fn holder_count_is_zero_without_supply() {
+            let erc20 = Erc20::new(0);
+
+            assert_eq!(erc20.holder_count(), 0);
+        }
+
+        #[ink::test]
+        fn permit_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (secret, owner) = keypair(1);
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(owner, 500), Ok(()));
+
+            let signature = sign(&secret, erc20.permit_hash(owner, accounts.bob, 100, 1000));
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1000, signature),
+                Ok(())
+            );
+
+            assert_eq!(erc20.allowance(owner, accounts.bob), 100);
+            assert_eq!(erc20.nonce_of(owner), 1);
+            assert_eq!(erc20.balance_of(owner), 500);
+        }
+
+        #[ink::test]
+        fn permit_failed_with_permitexpired() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (secret, owner) = keypair(1);
+
+            let mut erc20 = Erc20::new(1000);
+            let signature = sign(&secret, erc20.permit_hash(owner, accounts.bob, 100, 0));
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 0, signature),
+                Err(Error::PermitExpired)
+            );
+
+            assert_eq!(erc20.allowance(owner, accounts.bob), 0);
+            assert_eq!(erc20.nonce_of(owner), 0);
+        }
+
+        #[ink::test]
+        fn permit_replay_failed_with_invalidsignature() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (secret, owner) = keypair(1);
+
+            let mut erc20 = Erc20::new(1000);
+            let signature = sign(&secret, erc20.permit_hash(owner, accounts.bob, 100, 1000));
+
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1000, signature),
+                Ok(())
+            );
+
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1000, signature),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(erc20.nonce_of(owner), 1);
+        }
+
+        #[ink::test]
+        fn permit_from_other_signer_failed_with_invalidsignature() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (_, owner) = keypair(1);
+            let (other, _) = keypair(2);
+
+            let mut erc20 = Erc20::new(1000);
+            let signature = sign(&other, erc20.permit_hash(owner, accounts.bob, 100, 1000));
+
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1000, signature),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(erc20.allowance(owner, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn snapshot_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.transfer(accounts.bob, 200), Ok(()));
+            assert_eq!(erc20.burn(100), Ok(()));
+
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), Ok(900));
+            assert_eq!(erc20.balance_of_at(accounts.bob, 1), Ok(100));
+            assert_eq!(erc20.balance_of_at(accounts.charlie, 1), Ok(0));
+            assert_eq!(erc20.total_supply_at(1), Ok(1000));
+
+            assert_eq!(erc20.snapshot(), Ok(2));
+            assert_eq!(erc20.transfer(accounts.charlie, 50), Ok(()));
+
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), Ok(900));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 2), Ok(600));
+            assert_eq!(erc20.balance_of_at(accounts.bob, 2), Ok(300));
+            assert_eq!(erc20.balance_of_at(accounts.charlie, 2), Ok(0));
+            assert_eq!(erc20.total_supply_at(2), Ok(900));
+            assert_eq!(erc20.balance_of(accounts.alice), 550);
+        }
+
+        #[ink::test]
+        fn total_supply_at_works_across_quiet_snapshots() {
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.snapshot(), Ok(2));
+            assert_eq!(erc20.snapshot(), Ok(3));
+            assert_eq!(erc20.burn(100), Ok(()));
+            assert_eq!(erc20.burn(100), Ok(()));
+            assert_eq!(erc20.snapshot(), Ok(4));
+
+            assert_eq!(erc20.total_supply_at(1), Ok(1000));
+            assert_eq!(erc20.total_supply_at(3), Ok(1000));
+            assert_eq!(erc20.total_supply_at(4), Ok(800));
+
+            assert_eq!(erc20.burn(100), Ok(()));
+            assert_eq!(erc20.total_supply_at(2), Ok(1000));
+            assert_eq!(erc20.total_supply_at(4), Ok(800));
+            assert_eq!(erc20.total_supply(), 700);
+        }
+
+        #[ink::test]
+        fn snapshot_failed_with_invalidsnapshot() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(
+                erc20.balance_of_at(accounts.alice, 0),
+                Err(Error::InvalidSnapshot)
+            );
+            assert_eq!(erc20.total_supply_at(1), Err(Error::InvalidSnapshot));
+
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.total_supply_at(1), Ok(1000));
+            assert_eq!(erc20.total_supply_at(2), Err(Error::InvalidSnapshot));
+        }
+
+        #[ink::test]
+        fn snapshot_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.snapshot(), Err(Error::NotIssuer));
+        }
+
+        #[cfg(feature = "audit")]
+        #[ink::test]
+        fn verify_supply_invariant_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert!(erc20.verify_supply_invariant());
+
+            assert_eq!(erc20.transfer(accounts.bob, 300), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 200), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 200),
+                Ok(())
+            );
+            assert_eq!(erc20.transfer(accounts.alice, 300), Ok(()));
+            assert_eq!(erc20.burn(0), Ok(()));
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.burn(100), Ok(()));
+            assert_eq!(erc20.issue(50), Ok(()));
+
+            assert!(erc20.verify_supply_invariant());
+            assert_eq!(erc20.total_supply(), 950);
+        }
+
+        #[ink::test]
+        fn transfer_from_keeps_infinite_allowance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.approve(accounts.bob, Balance::MAX), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 500), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 300),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), Balance::MAX);
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 300),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 200);
+
+            assert_eq!(erc20.balance_of(accounts.alice), 400);
+            assert_eq!(erc20.balance_of(accounts.bob), 300);
+            assert_eq!(erc20.balance_of(accounts.charlie), 300);
+        }
+
+        #[ink::test]
+        fn multisig_issue_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_multisig(
+                1000,
+                vec![accounts.alice, accounts.bob, accounts.charlie],
+                2,
+            );
+
+            assert_eq!(erc20.issue(100), Err(Error::ThresholdNotMet));
+            assert_eq!(erc20.propose_issue(100), Ok(0));
+            assert_eq!(erc20.confirm(0), Err(Error::AlreadyConfirmed));
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.proposals().len(), 1);
+            assert_eq!(erc20.proposals()[0].1.confirmations, vec![accounts.alice]);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.confirm(0), Ok(()));
+
+            assert_eq!(erc20.total_supply(), 1100);
+            assert_eq!(erc20.balance_of(accounts.alice), 1100);
+            assert_eq!(erc20.proposals(), vec![]);
+
+            set_caller(accounts.charlie);
+            assert_eq!(erc20.confirm(0), Err(Error::ProposalNotFound));
+        }
+
+        #[ink::test]
+        fn multisig_pause_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_multisig(
+                1000,
+                vec![accounts.alice, accounts.bob, accounts.charlie],
+                2,
+            );
+
+            assert_eq!(erc20.pause(), Err(Error::ThresholdNotMet));
+
+            set_caller(accounts.charlie);
+            assert_eq!(erc20.propose_pause(), Ok(0));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.confirm(0), Ok(()));
+            assert_eq!(erc20.transfer(accounts.alice, 0), Err(Error::Paused));
+
+            assert_eq!(erc20.propose_unpause(), Ok(1));
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.confirm(1), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+        }
+
+        #[ink::test]
+        fn multisig_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_multisig(
+                1000,
+                vec![accounts.alice, accounts.bob, accounts.charlie],
+                2,
+            );
+            assert_eq!(erc20.propose_issue(100), Ok(0));
+
+            set_caller(accounts.django);
+            assert_eq!(erc20.issue(100), Err(Error::NotIssuer));
+            assert_eq!(erc20.propose_issue(100), Err(Error::NotIssuer));
+            assert_eq!(erc20.confirm(0), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "threshold must be between 1 and the number of issuers")]
+        fn create_multisig_contract_with_unreachable_threshold_fails() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            Erc20::new_multisig(1000, vec![accounts.alice, accounts.bob], 3);
+        }
+
+        #[ink::test]
+        fn timelocked_issue_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 2);
+
+            assert_eq!(erc20.issue(100), Ok(()));
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.pending_issue(0), Some((accounts.alice, 100, 2)));
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.execute_issue(0), Err(Error::TimelockNotElapsed));
+            assert_eq!(erc20.total_supply(), 1000);
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            assert_eq!(erc20.execute_issue(0), Ok(()));
+            assert_eq!(erc20.total_supply(), 1100);
+            assert_eq!(erc20.balance_of(accounts.alice), 1100);
+            assert_eq!(erc20.pending_issue(0), None);
+
+            assert_eq!(erc20.execute_issue(0), Err(Error::IssueNotFound));
+        }
+
+        #[ink::test]
+        fn timelocked_issue_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 2);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue(100), Err(Error::NotIssuer));
+            assert_eq!(erc20.pending_issue(0), None);
+        }
+
+        #[ink::test]
+        fn transfer_with_memo_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(
+                erc20.transfer_with_memo(accounts.bob, 100, b"deposit-42".to_vec()),
+                Ok(())
+            );
+
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert!(matches!(recorded_event(1), Event::Transfer(_)));
+            assert!(matches!(recorded_event(2), Event::TransferDetailed(_)));
+            match recorded_event(3) {
+                Event::TransferMemo(TransferMemo {
+                    from,
+                    to,
+                    value,
+                    memo,
+                }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(to, accounts.bob);
+                    assert_eq!(value, 100);
+                    assert_eq!(memo, b"deposit-42".to_vec());
+                }
+                _ => panic!("Expected a TransferMemo event"),
+            }
+        }
+
+        #[ink::test]
+        fn transfer_with_memo_failed_with_memotoolong() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(
+                erc20.transfer_with_memo(accounts.bob, 100, vec![0x0; MAX_MEMO_LEN]),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.transfer_with_memo(accounts.bob, 100, vec![0x0; MAX_MEMO_LEN + 1]),
+                Err(Error::MemoTooLong)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn issuer_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.issuer(), accounts.alice);
+
+            assert_eq!(erc20.transfer_issuership(accounts.bob), Ok(()));
+            assert_eq!(erc20.issuer(), accounts.bob);
+        }
+
+        #[ink::test]
+        fn renounce_issuership_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
+
+            assert_eq!(erc20.issuer(), AccountId::from([0x0; 32]));
+            assert_eq!(erc20.issue(100), Err(Error::NotIssuer));
+            assert_eq!(
+                erc20.transfer_issuership(accounts.alice),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.renounce_issuership(), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn renounce_issuership_drops_queued_issues() {
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 1);
+
+            assert_eq!(erc20.issue(100), Ok(()));
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(erc20.execute_issue(0), Err(Error::IssueNotFound));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn renounce_issuership_disables_multisig() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_multisig(1000, vec![accounts.bob, accounts.charlie], 2);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.propose_issue(100), Ok(0));
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
+
+            set_caller(accounts.charlie);
+            assert_eq!(erc20.confirm(0), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn renounce_issuership_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.renounce_issuership(), Err(Error::NotIssuer));
+            assert_eq!(erc20.issuer(), accounts.alice);
+        }
+
+        #[ink::test]
+        fn distribute_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 250), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 150), Ok(()));
+            assert_eq!(erc20.transfer(accounts.django, 10), Ok(()));
+
+            set_caller(accounts.django);
+            assert_eq!(erc20.transfer(accounts.alice, 10), Ok(()));
+
+            set_caller(accounts.alice);
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.distribute(7), Ok(()));
+
+            // 600 / 250 / 150 of 1000 earn 4.2 / 1.75 / 1.05, rounded down to
+            // 4 / 1 / 1 with one unit of dust for the issuer.
+            assert_eq!(erc20.balance_of(accounts.alice), 605);
+            assert_eq!(erc20.balance_of(accounts.bob), 251);
+            assert_eq!(erc20.balance_of(accounts.charlie), 151);
+            assert_eq!(erc20.balance_of(accounts.django), 0);
+            assert_eq!(erc20.total_supply(), 1007);
+            assert_eq!(ink_env::test::recorded_events().count() - events_before, 5);
+        }
+
+        #[ink::test]
+        fn distribute_skips_escrow_and_dead() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new_with_burn_to_address(1000, true);
+            assert_eq!(erc20.transfer(accounts.bob, 400), Ok(()));
+            assert_eq!(erc20.create_vesting(accounts.charlie, 100, 10), Ok(()));
+            assert_eq!(erc20.burn(100), Ok(()));
+
+            // Alice and Bob hold 400 each of the 800 outside escrow and DEAD.
+            assert_eq!(erc20.distribute(80), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 440);
+            assert_eq!(erc20.balance_of(accounts.bob), 440);
+            assert_eq!(erc20.balance_of(accounts.eve), 100);
+            assert_eq!(erc20.balance_of(DEAD.into()), 100);
+            assert_eq!(erc20.total_supply(), 1080);
+        }
+
+        #[ink::test]
+        fn distribute_works_with_large_supply() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(10u128.pow(27));
+            assert_eq!(erc20.transfer(accounts.bob, 10u128.pow(26)), Ok(()));
+
+            assert_eq!(erc20.distribute(10u128.pow(21)), Ok(()));
+
+            assert_eq!(
+                erc20.balance_of(accounts.bob),
+                10u128.pow(26) + 10u128.pow(20)
+            );
+            assert_eq!(
+                erc20.balance_of(accounts.alice),
+                9 * 10u128.pow(26) + 9 * 10u128.pow(20)
+            );
+            assert_eq!(erc20.total_supply(), 10u128.pow(27) + 10u128.pow(21));
+        }
+
+        #[ink::test]
+        fn distribute_waits_for_mint_delay() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 2);
+            assert_eq!(erc20.transfer(accounts.bob, 250), Ok(()));
+
+            assert_eq!(erc20.distribute(100), Ok(()));
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 250);
+            assert_eq!(erc20.pending_issue(0), Some((accounts.alice, 75, 2)));
+            assert_eq!(erc20.pending_issue(1), Some((accounts.bob, 25, 2)));
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            assert_eq!(erc20.execute_issue(0), Ok(()));
+            assert_eq!(erc20.execute_issue(1), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 825);
+            assert_eq!(erc20.balance_of(accounts.bob), 275);
+            assert_eq!(erc20.total_supply(), 1100);
+        }
+
+        #[ink::test]
+        fn distribute_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.distribute(100), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn distribute_failed_with_capexceeded() {
+            let mut erc20 = Erc20::new_capped(1000, 1050);
+
+            assert_eq!(erc20.distribute(51), Err(Error::CapExceeded));
+            assert_eq!(erc20.distribute(50), Ok(()));
+            assert_eq!(erc20.total_supply(), 1050);
+        }
+
+        #[ink::test]
+        fn transfer_and_call_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            mock_contract(accounts.charlie, || true);
+
+            assert_eq!(
+                erc20.transfer_and_call(accounts.bob, 10, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.transfer_and_call(accounts.charlie, 20, vec![1, 2]),
+                Ok(())
+            );
+
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 20);
+            assert_eq!(
+                mock_calls(),
+                vec![(
+                    accounts.charlie,
+                    Callback::OnTokenTransfer(accounts.alice, 20, vec![1, 2])
+                )]
+            );
+        }
+
+        #[ink::test]
+        fn transfer_and_call_failed_with_callbackfailed() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            mock_contract(accounts.charlie, || false);
+
+            // On chain the failed message reverts the transfer as well.
+            assert_eq!(
+                erc20.transfer_and_call(accounts.charlie, 20, Vec::new()),
+                Err(Error::CallbackFailed)
+            );
+            assert!(!erc20.entered);
+        }
+
+        #[ink::test]
+        fn transfer_and_call_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(
+                erc20.transfer_and_call(accounts.bob, 101, Vec::new()),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn force_transfer_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 40), Ok(()));
+            assert_eq!(erc20.freeze(accounts.bob), Ok(()));
+
+            assert_eq!(
+                erc20.force_transfer(accounts.bob, accounts.charlie, 30),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 30);
+            assert_eq!(
+                erc20.force_transfer(accounts.bob, accounts.charlie, 11),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        fn force_transfer_keeps_escrow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(200);
+            assert_eq!(erc20.create_vesting(accounts.bob, 100, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.eve, 5), Ok(()));
+
+            assert_eq!(
+                erc20.force_transfer(accounts.eve, accounts.alice, 100),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(
+                erc20.force_transfer(accounts.eve, accounts.alice, 5),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.eve), 100);
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn force_transfer_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.force_transfer(accounts.alice, accounts.bob, 10),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn force_transfer_failed_with_zeroaddress() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+
+            assert_eq!(
+                erc20.force_transfer(accounts.bob, AccountId::from([0; 32]), 10),
+                Err(Error::ZeroAddress)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 30);
+        }
+
+        #[ink::test]
+        fn circulating_supply_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.treasury(), accounts.alice);
+            assert_eq!(erc20.circulating_supply(), 0);
+
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.circulating_supply(), 30);
+
+            assert_eq!(erc20.set_treasury(accounts.charlie), Ok(()));
+            assert_eq!(erc20.treasury(), accounts.charlie);
+            assert_eq!(erc20.circulating_supply(), 100);
+
+            assert_eq!(erc20.transfer(accounts.charlie, 50), Ok(()));
+            assert_eq!(erc20.circulating_supply(), 50);
+        }
+
+        #[ink::test]
+        fn set_treasury_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.set_treasury(accounts.bob), Err(Error::NotIssuer));
+            assert_eq!(erc20.treasury(), accounts.alice);
+        }
+
+        #[ink::test]
+        fn spendable_by_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 40), Ok(()));
+            assert_eq!(erc20.spendable_by(accounts.alice, accounts.bob), 40);
+
+            assert_eq!(erc20.transfer(accounts.charlie, 70), Ok(()));
+            assert_eq!(erc20.spendable_by(accounts.alice, accounts.bob), 30);
+            assert_eq!(erc20.spendable_by(accounts.alice, accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn claim_vested_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.create_vesting(accounts.bob, 60, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 40);
+            assert_eq!(erc20.balance_of(accounts.eve), 60);
+            assert_eq!(erc20.vesting_of(accounts.bob), Some((60, 0, 10)));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.claim_vested(), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+
+            for _ in 0..5 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(erc20.claim_vested(), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 30);
+
+            for _ in 0..5 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(erc20.claim_vested(), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 60);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            assert_eq!(erc20.vesting_of(accounts.bob), None);
+            assert_eq!(erc20.claim_vested(), Err(Error::VestingNotFound));
+        }
+
+        #[ink::test]
+        fn create_vesting_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.create_vesting(accounts.charlie, 10, 10),
+                Err(Error::NotIssuer)
+            );
+        }
+
+        #[ink::test]
+        fn create_vesting_failed_with_vestingexists() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.create_vesting(accounts.bob, 10, 10), Ok(()));
+            assert_eq!(
+                erc20.create_vesting(accounts.bob, 10, 10),
+                Err(Error::VestingExists)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+        }
+
+        #[ink::test]
+        fn cooldown_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_cooldown(3), Ok(()));
+            assert_eq!(erc20.cooldown_blocks(), 3);
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 10), Err(Error::CooldownActive));
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 1), (accounts.charlie, 1)]),
+                Err(Error::CooldownActive)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+
+            for _ in 0..3 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 1), (accounts.charlie, 1)]),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 11);
+            assert_eq!(erc20.balance_of(accounts.charlie), 1);
+            assert_eq!(erc20.transfer(accounts.bob, 10), Err(Error::CooldownActive));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 1), Ok(()));
+        }
+
+        #[ink::test]
+        fn set_cooldown_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.set_cooldown(3), Err(Error::NotIssuer));
+            assert_eq!(erc20.cooldown_blocks(), 0);
+        }
+
+        #[ink::test]
+        fn allowances_of_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 20), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 15), Ok(()));
+            assert_eq!(
+                erc20.allowances_of(accounts.alice),
+                vec![(accounts.bob, 15), (accounts.charlie, 20)]
+            );
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 20),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.allowances_of(accounts.alice),
+                vec![(accounts.bob, 15)]
+            );
+            assert_eq!(erc20.allowances_of(accounts.bob), vec![]);
+        }
+
+        #[ink::test]
+        fn clawback_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.set_clawback_window(2), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.django, 20), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.clawback(accounts.bob), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 40);
+            assert_eq!(erc20.balance_of(accounts.charlie), 10);
+            assert_eq!(erc20.balance_of(accounts.django), 0);
+            assert_eq!(
+                erc20.clawback(accounts.bob),
+                Err(Error::ClawbackWindowClosed)
+            );
+        }
+
+        #[ink::test]
+        fn clawback_keeps_escrow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(200);
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.set_clawback_window(2), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.eve, 10), Ok(()));
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.create_vesting(accounts.charlie, 100, 10), Ok(()));
+            assert_eq!(erc20.force_transfer(accounts.eve, accounts.bob, 10), Ok(()));
+
+            assert_eq!(
+                erc20.clawback(accounts.bob),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.balance_of(accounts.eve), 100);
+            assert_eq!(erc20.balance_of(accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn clawback_failed_with_clawbackwindowclosed() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_clawback_window(2), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+            for _ in 0..3 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+
+            assert_eq!(
+                erc20.clawback(accounts.alice),
+                Err(Error::ClawbackWindowClosed)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn clawback_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_clawback_window(2), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.clawback(accounts.alice), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn min_balance_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.set_min_balance(10), Ok(()));
+            assert_eq!(erc20.min_balance(), 10);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 40), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn transfer_failed_with_dustremainder() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_min_balance(10), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.bob, 91), Err(Error::DustRemainder));
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 45), (accounts.charlie, 46)]),
+                Err(Error::DustRemainder)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn set_events_enabled_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert!(erc20.events_enabled());
+            assert_eq!(erc20.set_events_enabled(false), Ok(()));
+
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count(), events_before);
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+
+            assert_eq!(erc20.set_events_enabled(true), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count(), events_before + 2);
+        }
+
+        #[ink::test]
+        fn set_events_enabled_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.set_events_enabled(false), Err(Error::NotIssuer));
+            assert!(erc20.events_enabled());
+        }
+
+        #[ink::test]
+        fn balances_of_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+
+            assert_eq!(
+                erc20.balances_of(vec![accounts.bob, accounts.charlie, accounts.alice]),
+                Ok(vec![30, 0, 70])
+            );
+        }
+
+        #[ink::test]
+        fn balances_of_failed_with_batchtoolarge() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new(100);
+
+            assert_eq!(
+                erc20
+                    .balances_of(vec![accounts.bob; MAX_BATCH_LEN])
+                    .map(|balances| balances.len()),
+                Ok(MAX_BATCH_LEN)
+            );
+            assert_eq!(
+                erc20.balances_of(vec![accounts.bob; MAX_BATCH_LEN + 1]),
+                Err(Error::BatchTooLarge)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_fee_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(10_000);
+            assert_eq!(erc20.transfer(accounts.bob, 2_000), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 100), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.charlie), 100);
+            assert_eq!(erc20.balance_of(accounts.alice), 8_000);
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.set_fee_bps(250), Ok(()));
+            assert_eq!(erc20.fee_bps(), 250);
+
+            set_caller(accounts.bob);
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.transfer(accounts.charlie, 1_000), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count() - events_before, 3);
+            assert_eq!(erc20.balance_of(accounts.bob), 900);
+            assert_eq!(erc20.balance_of(accounts.charlie), 1_075);
+            assert_eq!(erc20.balance_of(accounts.alice), 8_025);
+
+            // 2.5% of 99 is 2.475, rounded down to 2.
+            assert_eq!(erc20.transfer(accounts.charlie, 99), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 801);
+            assert_eq!(erc20.balance_of(accounts.charlie), 1_172);
+            assert_eq!(erc20.balance_of(accounts.alice), 8_027);
+            assert_eq!(erc20.total_supply(), 10_000);
+        }
+
+        #[ink::test]
+        fn transfer_from_fee_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 200), Ok(()));
+            assert_eq!(erc20.set_fee_bps(1_000), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.approve(accounts.charlie, 100), Ok(()));
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.bob, accounts.django, 100),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.django), 90);
+            assert_eq!(erc20.balance_of(accounts.alice), 810);
+            assert_eq!(erc20.allowance(accounts.bob, accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn transfer_from_runs_transfer_checks() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.set_cooldown(3), Ok(()));
+            assert_eq!(erc20.set_min_balance(10), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 95),
+                Err(Error::DustRemainder)
+            );
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 10),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 10),
+                Err(Error::CooldownActive)
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 10);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 90);
+        }
+
+        #[ink::test]
+        fn set_fee_bps_failed_with_invalidfee() {
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(erc20.set_fee_bps(MAX_FEE_BPS), Ok(()));
+            assert_eq!(erc20.set_fee_bps(MAX_FEE_BPS + 1), Err(Error::InvalidFee));
+            assert_eq!(erc20.fee_bps(), MAX_FEE_BPS);
+        }
+
+        #[ink::test]
+        fn create_contract_with_allocations_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new_with_allocations(vec![
+                (accounts.bob, 50),
+                (accounts.alice, 30),
+                (accounts.charlie, 20),
+            ]);
+
+            assert_eq!(erc20.total_supply(), 100);
+            assert_eq!(erc20.issuer(), accounts.alice);
+            assert_eq!(erc20.balance_of(accounts.alice), 30);
+            assert_eq!(erc20.balance_of(accounts.bob), 50);
+            assert_eq!(erc20.balance_of(accounts.charlie), 20);
+            assert_eq!(ink_env::test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "duplicate allocation")]
+        fn create_contract_with_duplicate_allocations_fails() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            Erc20::new_with_allocations(vec![(accounts.bob, 50), (accounts.bob, 20)]);
+        }
+
+        #[ink::test]
+        fn self_transfer_from_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.self_transfer_from(accounts.bob, accounts.charlie, 40),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 60);
+            assert_eq!(erc20.balance_of(accounts.charlie), 40);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn self_transfer_from_keeps_allowance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 25), Ok(()));
+            assert_eq!(
+                erc20.self_transfer_from(accounts.bob, accounts.charlie, 40),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 40);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 25);
+        }
+
+        #[ink::test]
+        fn self_transfer_from_works_with_strict_approve() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_strict_approve(true), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 25), Ok(()));
+            assert_eq!(
+                erc20.self_transfer_from(accounts.bob, accounts.charlie, 40),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 40);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 25);
+        }
+
+        #[ink::test]
+        fn self_transfer_from_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+
+            assert_eq!(
+                erc20.self_transfer_from(accounts.bob, accounts.charlie, 101),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn balance_of_scaled_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new_with_metadata(1000, None, None, 6);
+
+            assert_eq!(erc20.balance_of_scaled(accounts.alice), (1000, 6));
+            assert_eq!(erc20.balance_of_scaled(accounts.bob), (0, 6));
+        }
+
+        #[ink::test]
+        fn transfer_and_call_failed_with_reentrancy() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            // Bob's callback starts another transfer before the first returns.
+            let reentered = std::rc::Rc::new(core::cell::Cell::new(None));
+            let inner = reentered.clone();
+            let charlie = accounts.charlie;
+            mock_contract(accounts.bob, move || {
+                inner.set(Some(reenter(|token| {
+                    token.transfer_and_call(charlie, 10, Vec::new())
+                })));
+                true
+            });
+
+            assert_eq!(
+                erc20.transfer_and_call(accounts.bob, 10, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(reentered.take(), Some(Err(Error::Reentrancy)));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+
+            assert_eq!(
+                erc20.transfer_and_call(accounts.bob, 101, Vec::new()),
+                Err(Error::InsufficientBalance)
+            );
+            assert!(!erc20.entered);
+        }
+
+        #[ink::test]
+        fn has_interacted_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert!(erc20.has_interacted(accounts.alice));
+            assert!(!erc20.has_interacted(accounts.bob));
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert!(erc20.has_interacted(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.alice, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert!(erc20.has_interacted(accounts.bob));
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.approve(accounts.charlie, 10), Ok(()));
+            assert!(!erc20.has_interacted(accounts.charlie));
+            assert_eq!(erc20.issue(10), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 10),
+                Ok(())
+            );
+            assert!(erc20.has_interacted(accounts.django));
+        }
+
+        #[ink::test]
+        fn approve_batch_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            let events_before = ink_env::test::recorded_events().count();
+
+            assert_eq!(
+                erc20.approve_batch(vec![(accounts.bob, 10), (accounts.charlie, 20)]),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 20);
+            assert_eq!(ink_env::test::recorded_events().count() - events_before, 2);
+        }
+
+        #[ink::test]
+        fn approve_batch_failed_with_duplicatespender() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(
+                erc20.approve_batch(vec![
+                    (accounts.bob, 10),
+                    (accounts.charlie, 20),
+                    (accounts.bob, 30)
+                ]),
+                Err(Error::DuplicateSpender)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn domain_separator_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new(100);
+            assert_eq!(erc20.domain_separator(), erc20.domain_separator());
+            assert_eq!(erc20.domain_separator(), Erc20::new(100).domain_separator());
+
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+            let other = Erc20::new(100);
+            assert_ne!(other.domain_separator(), erc20.domain_separator());
+        }
+
+        #[ink::test]
+        fn recent_transfers_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_recent_transfers_cap(100, 3);
+            assert_eq!(erc20.recent_transfers(10), vec![]);
+
+            for value in 1..=4 {
+                assert_eq!(erc20.transfer(accounts.bob, value), Ok(()));
+            }
+            assert_eq!(erc20.approve(accounts.bob, 5), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 5),
+                Ok(())
+            );
+
+            let recent: Vec<_> = erc20
+                .recent_transfers(10)
+                .into_iter()
+                .map(|(from, to, value, _)| (from, to, value))
+                .collect();
+            assert_eq!(
+                recent,
+                vec![
+                    (accounts.alice, accounts.charlie, 5),
+                    (accounts.alice, accounts.bob, 4),
+                    (accounts.alice, accounts.bob, 3),
+                ]
+            );
+            assert_eq!(erc20.recent_transfers(1).len(), 1);
+        }
+
+        #[ink::test]
+        fn issue_to_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.issue_to(accounts.bob, 50), Ok(()));
+
+            assert_eq!(erc20.total_supply(), 150);
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(accounts.bob), 50);
+
+            let mut capped = Erc20::new_capped(100, 120);
+            assert_eq!(capped.issue_to(accounts.bob, 21), Err(Error::CapExceeded));
+        }
+
+        #[ink::test]
+        fn issue_to_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue_to(accounts.bob, 50), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn sum_balances_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
+            assert_eq!(erc20.issue_to(accounts.django, 5), Ok(()));
+            assert_eq!(erc20.burn(10), Ok(()));
+
+            assert_eq!(erc20.sum_balances(), Ok(95));
+            assert_eq!(erc20.sum_balances(), Ok(erc20.total_supply()));
+        }
+
+        #[ink::test]
+        fn allowlist_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_allowlist_enabled(true), Ok(()));
+            assert_eq!(erc20.allow(accounts.alice), Ok(()));
+            assert_eq!(erc20.allow(accounts.bob), Ok(()));
+            assert!(erc20.is_allowed(accounts.bob));
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 10), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 10),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 20);
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.disallow(accounts.bob), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 10), Err(Error::NotAllowlisted));
+
+            assert_eq!(erc20.set_allowlist_enabled(false), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+        }
+
+        #[ink::test]
+        fn transfer_failed_with_notallowlisted() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_allowlist_enabled(true), Ok(()));
+            assert_eq!(erc20.allow(accounts.alice), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Err(Error::NotAllowlisted));
+            assert_eq!(erc20.approve(accounts.charlie, 10), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 10),
+                Err(Error::NotAllowlisted)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn allow_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.allow(accounts.bob), Err(Error::NotIssuer));
+            assert_eq!(erc20.set_allowlist_enabled(true), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn terminate_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+
+            let should_terminate = move || {
+                erc20.terminate().expect("terminate does not return");
+            };
+            ink_env::test::assert_contract_termination::<ink_env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.alice,
+                0,
+            );
+        }
+
+        #[ink::test]
+        fn terminate_works_with_events_disabled() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_events_enabled(false), Ok(()));
+
+            let should_terminate = move || {
+                erc20.terminate().expect("terminate does not return");
+            };
+            ink_env::test::assert_contract_termination::<ink_env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.alice,
+                0,
+            );
+            match recorded_event(1) {
+                Event::Burn(Burn { from, value }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(value, 100);
+                }
+                _ => panic!("Expected a Burn event"),
+            }
+        }
+
+        #[ink::test]
+        fn terminate_failed_with_escrownotempty() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.create_vesting(accounts.bob, 40, 10), Ok(()));
+
+            assert_eq!(erc20.terminate(), Err(Error::EscrowNotEmpty));
+            assert_eq!(erc20.balance_of(accounts.eve), 40);
+        }
+
+        #[ink::test]
+        fn terminate_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.terminate(), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn can_spend_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 10));
+
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert!(erc20.can_spend(accounts.alice, accounts.bob, 10));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 11));
+
+            assert_eq!(erc20.pause(), Ok(()));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 10));
+            assert_eq!(erc20.unpause(), Ok(()));
+
+            assert_eq!(erc20.freeze(accounts.bob), Ok(()));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 10));
+            assert_eq!(erc20.unfreeze(accounts.bob), Ok(()));
+
+            assert_eq!(erc20.freeze(accounts.alice), Ok(()));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 10));
+            assert_eq!(erc20.unfreeze(accounts.alice), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.charlie, 95), Ok(()));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 10));
+            assert!(erc20.can_spend(accounts.alice, accounts.bob, 5));
+        }
+
+        #[ink::test]
+        fn can_spend_follows_transfer_from() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(erc20.lock(accounts.alice, 60), Ok(()));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 41));
+            assert!(erc20.can_spend(accounts.alice, accounts.bob, 40));
+            assert_eq!(erc20.lock(accounts.alice, 0), Ok(()));
+
+            assert_eq!(erc20.set_spender_whitelist_enabled(true), Ok(()));
+            assert!(!erc20.can_spend(accounts.alice, accounts.bob, 10));
+            assert_eq!(erc20.whitelist_spender(accounts.bob), Ok(()));
+            assert!(erc20.can_spend(accounts.alice, accounts.bob, 10));
+        }
+
+        #[ink::test]
+        fn burn_percent_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 1), Ok(()));
+
+            assert_eq!(erc20.burn_percent(5_000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 50);
+
+            // Half of 49 is 24.5, rounded down to 24.
+            assert_eq!(erc20.transfer(accounts.bob, 1), Ok(()));
+            assert_eq!(erc20.burn_percent(5_000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 25);
+
+            assert_eq!(erc20.burn_percent(BASIS_POINTS), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 0);
+            assert_eq!(erc20.total_supply(), 2);
+        }
+
+        #[ink::test]
+        fn burn_percent_failed_with_invalidpercent() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(erc20.burn_percent(15_000), Err(Error::InvalidPercent));
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn rescue_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.create_vesting(accounts.django, 30, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 20), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.eve, 20), Ok(()));
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                erc20.rescue(accounts.bob, 21),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.rescue(accounts.bob, 20), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 20);
+            assert_eq!(erc20.balance_of(accounts.eve), 30);
+        }
+
+        #[ink::test]
+        fn rescue_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.eve, 20), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.rescue(accounts.bob, 20), Err(Error::NotIssuer));
+            assert_eq!(erc20.balance_of(accounts.eve), 20);
+        }
+
+        #[ink::test]
+        fn minter_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.add_minter(accounts.bob), Ok(()));
+            assert!(erc20.is_minter(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue(10), Ok(()));
+            assert_eq!(erc20.issue_to(accounts.charlie, 5), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 5);
+            assert_eq!(erc20.total_supply(), 115);
+            assert_eq!(erc20.pause(), Err(Error::NotIssuer));
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.add_minter(accounts.charlie), Ok(()));
+            assert_eq!(erc20.add_minter(accounts.django), Ok(()));
+            assert_eq!(
+                erc20.minters(),
+                vec![accounts.bob, accounts.charlie, accounts.django]
+            );
+            assert_eq!(erc20.remove_minter(accounts.bob), Ok(()));
+            assert_eq!(erc20.minters(), vec![accounts.django, accounts.charlie]);
+            assert_eq!(erc20.remove_minter(accounts.charlie), Ok(()));
+            assert_eq!(erc20.remove_minter(accounts.django), Ok(()));
+            assert_eq!(erc20.minters(), vec![]);
+            assert!(!erc20.is_minter(accounts.django));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue(10), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn add_minter_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.add_minter(accounts.bob), Err(Error::NotIssuer));
+            assert!(!erc20.is_minter(accounts.bob));
+        }
+
+        #[ink::test]
+        fn renounce_issuership_drops_minters() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.add_minter(accounts.bob), Ok(()));
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
+            assert_eq!(erc20.minters(), vec![]);
+            assert!(!erc20.is_minter(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.issue(10), Err(Error::NotIssuer));
+        }
+
+        #[ink::test]
+        fn transfer_str_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(erc20.transfer_str(accounts.bob, String::from("42")), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 42);
+        }
+
+        #[ink::test]
+        fn transfer_str_failed_with_parseamount() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            for amount in ["", "ten", "+10", "-1", "1.5"] {
+                assert_eq!(
+                    erc20.transfer_str(accounts.bob, String::from(amount)),
+                    Err(Error::ParseAmount)
+                );
+            }
+            assert_eq!(
+                erc20.transfer_str(
+                    accounts.bob,
+                    String::from("340282366920938463463374607431768211456")
+                ),
+                Err(Error::ParseAmount)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn strict_approve_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 20), Ok(()));
+
+            assert_eq!(erc20.set_strict_approve(true), Ok(()));
+            assert!(erc20.strict_approve());
+            assert_eq!(erc20.approve(accounts.bob, 0), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.increase_allowance(accounts.bob, 5), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 35);
+        }
+
+        #[ink::test]
+        fn approve_failed_with_approvefromnonzero() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_strict_approve(true), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+
+            assert_eq!(
+                erc20.approve(accounts.bob, 20),
+                Err(Error::ApproveFromNonZero)
+            );
+            assert_eq!(
+                erc20.approve_batch(vec![(accounts.charlie, 5), (accounts.bob, 20)]),
+                Err(Error::ApproveFromNonZero)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn supply_log_works() {
+            let mut erc20 = Erc20::new_with_supply_log_cap(100, 3);
+            assert_eq!(erc20.supply_log(10), vec![]);
+
+            assert_eq!(erc20.issue(10), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(erc20.burn(5), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(erc20.issue(20), Ok(()));
+            assert_eq!(erc20.supply_log(10), vec![(2, 125), (1, 105), (0, 110)]);
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(erc20.burn(25), Ok(()));
+            assert_eq!(erc20.supply_log(10), vec![(3, 100), (2, 125), (1, 105)]);
+            assert_eq!(erc20.supply_log(1), vec![(3, 100)]);
+        }
+
+        #[ink::test]
+        fn transfer_pending_runs_transfer_checks() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.lock(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.set_allowlist_enabled(true), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_pending(accounts.charlie, 100),
+                Err(Error::NotAllowlisted)
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.set_allowlist_enabled(false), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_pending(accounts.charlie, 100),
+                Err(Error::BalanceLocked)
+            );
+            assert_eq!(
+                erc20.transfer_pending(AccountId::from([0x0; 32]), 100),
+                Err(Error::ZeroAddress)
+            );
+
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.pending_transfer(accounts.bob, accounts.charlie), 0);
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.claim_pending(accounts.bob),
+                Err(Error::PendingNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn claim_pending_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer_pending(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.transfer_pending(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 60);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.pending_transfer(accounts.alice, accounts.bob), 40);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.claim_pending(accounts.alice), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 40);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            assert_eq!(erc20.pending_transfer(accounts.alice, accounts.bob), 0);
+            assert_eq!(
+                erc20.claim_pending(accounts.alice),
+                Err(Error::PendingNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn cancel_pending_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer_pending(accounts.bob, 30), Ok(()));
+
+            // Escrowed tokens are not rescuable.
+            assert_eq!(
+                erc20.rescue(accounts.charlie, 1),
+                Err(Error::InsufficientBalance)
+            );
+
+            assert_eq!(erc20.cancel_pending(accounts.bob), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.claim_pending(accounts.alice),
+                Err(Error::PendingNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn issue_to_failed_with_overflow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(Balance::MAX - 10);
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+
+            assert_eq!(erc20.issue_to(accounts.bob, 11), Err(Error::Overflow));
+            assert_eq!(erc20.total_supply(), Balance::MAX - 10);
+            assert_eq!(erc20.balance_of(accounts.alice), Balance::MAX - 20);
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+
+            assert_eq!(erc20.issue_to(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.total_supply(), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn timelocked_issue_failed_with_overflow() {
+            let mut erc20 = Erc20::new_with_mint_delay(Balance::MAX - 10, 5);
+
+            assert_eq!(erc20.issue(11), Err(Error::Overflow));
+            assert_eq!(erc20.pending_issue(0), None);
+            assert_eq!(erc20.total_supply(), Balance::MAX - 10);
+        }
+
+        #[ink::test]
+        fn simulate_transfer_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(10_000);
+            assert_eq!(erc20.transfer(accounts.bob, 2_000), Ok(()));
+            assert_eq!(erc20.set_fee_bps(250), Ok(()));
+
+            assert_eq!(
+                erc20.simulate_transfer(accounts.bob, accounts.charlie, 1_000),
+                Ok((1_000, 975))
+            );
+            assert_eq!(
+                erc20.simulate_transfer(accounts.alice, accounts.bob, 1_000),
+                Ok((7_025, 2_975))
+            );
+            assert_eq!(
+                erc20.simulate_transfer(accounts.bob, accounts.bob, 1_000),
+                Ok((1_975, 1_975))
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 2_000);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 1_000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 1_000);
+            assert_eq!(erc20.balance_of(accounts.charlie), 975);
+        }
+
+        #[ink::test]
+        fn simulate_transfer_works_with_auto_burn() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_auto_burn(100, accounts.frank);
+            assert_eq!(erc20.set_fee_bps(1_000), Ok(()));
+
+            assert_eq!(
+                erc20.simulate_transfer(accounts.alice, accounts.frank, 10),
+                Ok((90, 0))
+            );
+            assert_eq!(erc20.transfer(accounts.frank, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+            assert_eq!(erc20.balance_of(accounts.frank), 0);
+        }
+
+        #[ink::test]
+        fn simulate_transfer_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.simulate_transfer(accounts.alice, accounts.bob, 101),
+                Err(Error::InsufficientBalance)
+            );
+
+            assert_eq!(erc20.pause(), Ok(()));
+            assert_eq!(
+                erc20.simulate_transfer(accounts.alice, accounts.bob, 10),
+                Err(Error::Paused)
+            );
+        }
+
+        #[ink::test]
+        fn unit_size_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1_000);
+            assert_eq!(erc20.set_unit_size(100), Ok(()));
+            assert_eq!(erc20.unit_size(), 100);
+
+            assert_eq!(erc20.transfer(accounts.bob, 300), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 200), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 200),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 500);
+        }
+
+        #[ink::test]
+        fn transfer_failed_with_notunitaligned() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1_000);
+            assert_eq!(erc20.set_unit_size(100), Ok(()));
+
+            assert_eq!(
+                erc20.transfer(accounts.bob, 150),
+                Err(Error::NotUnitAligned)
+            );
+            assert_eq!(erc20.approve(accounts.charlie, 200), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 150),
+                Err(Error::NotUnitAligned)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn revoke_all_allowances_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 20), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(erc20.approve(accounts.charlie, 30), Ok(()));
+
+            set_caller(accounts.alice);
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.revoke_all_allowances(), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count() - events_before, 2);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 0);
+            assert_eq!(erc20.allowances_of(accounts.alice), vec![]);
+            assert_eq!(erc20.allowance(accounts.bob, accounts.charlie), 30);
+        }
+
+        #[ink::test]
+        fn transfer_from_removes_spent_allowance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 30), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 10),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.allowances.get((accounts.alice, accounts.bob)),
+                Some(20)
+            );
+
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 20),
+                Ok(())
+            );
+            assert_eq!(erc20.allowances.get((accounts.alice, accounts.bob)), None);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            assert_eq!(erc20.balance_of(accounts.charlie), 30);
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_all_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
+            assert_eq!(erc20.transfer_pending(accounts.django, 5), Ok(()));
+            assert_eq!(erc20.pause(), Ok(()));
+
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.emergency_withdraw_all(accounts.frank), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count() - events_before, 4);
+            assert_eq!(erc20.balance_of(accounts.alice), 0);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(erc20.balance_of(accounts.frank), 95);
+            assert_eq!(erc20.balance_of(accounts.eve), 5);
+            assert_eq!(erc20.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_all_failed_with_notpaused() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(
+                erc20.emergency_withdraw_all(accounts.frank),
+                Err(Error::NotPaused)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.emergency_withdraw_all(accounts.frank),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn set_name_works() {
+            let mut erc20 = Erc20::new_with_metadata(100, Some(String::from("Token")), None, 12);
+            let domain_separator = erc20.domain_separator();
+
+            assert_eq!(erc20.set_name(String::from("Rebrand")), Ok(()));
+            assert_eq!(erc20.token_name(), Some(String::from("Rebrand")));
+            assert_ne!(erc20.domain_separator(), domain_separator);
+            assert_eq!(erc20.set_symbol(String::from("RBD")), Ok(()));
+            assert_eq!(erc20.token_symbol(), Some(String::from("RBD")));
+            assert_eq!(erc20.token_decimals(), 12);
+        }
+
+        #[ink::test]
+        fn set_name_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_metadata(100, Some(String::from("Token")), None, 12);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.set_name(String::from("Rebrand")),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.set_symbol(String::from("RBD")), Err(Error::NotIssuer));
+            assert_eq!(erc20.token_name(), Some(String::from("Token")));
+            assert_eq!(erc20.token_symbol(), None);
+        }
+
+        #[cfg(feature = "hooks")]
+        #[ink::test]
+        fn transfer_hook_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
+            assert_eq!(HOOK_CALLS.with(|calls| calls.get()), (2, 2));
+
+            assert_eq!(
+                erc20.transfer(accounts.bob, 1000),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(HOOK_CALLS.with(|calls| calls.get()), (2, 2));
+        }
+
+        #[ink::test]
+        fn free_balance_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.free_balance(accounts.alice), 100);
+
+            assert_eq!(erc20.approve(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 20), Ok(()));
+            assert_eq!(erc20.free_balance(accounts.alice), 50);
+
+            assert_eq!(erc20.approve(accounts.django, 80), Ok(()));
+            assert_eq!(erc20.free_balance(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn transfer_deadline_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            let now = ink_env::block_number::<ink_env::DefaultEnvironment>();
+
+            assert_eq!(erc20.transfer_deadline(accounts.bob, 10, now), Ok(()));
+            assert_eq!(erc20.transfer_deadline(accounts.bob, 10, now + 5), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 20);
+        }
+
+        #[ink::test]
+        fn transfer_deadline_failed_with_deadlinepassed() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            let deadline = ink_env::block_number::<ink_env::DefaultEnvironment>() + 1;
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            assert_eq!(
+                erc20.transfer_deadline(accounts.bob, 10, deadline),
+                Err(Error::DeadlinePassed)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn burn_to_address_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_burn_to_address(100, true);
+
+            assert_eq!(erc20.burn(30), Ok(()));
+            assert_eq!(erc20.total_supply(), 100);
+            assert_eq!(erc20.balance_of(DEAD.into()), 30);
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+        }
+
+        #[ink::test]
+        fn burn_to_address_failed_with_paused() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_burn_to_address(100, true);
+            assert_eq!(erc20.pause(), Ok(()));
+            assert_eq!(erc20.burn(10), Err(Error::Paused));
+            assert_eq!(erc20.unpause(), Ok(()));
+
+            assert_eq!(erc20.freeze(accounts.alice), Ok(()));
+            assert_eq!(erc20.burn(10), Err(Error::AccountFrozen));
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(DEAD.into()), 0);
+        }
+
+        #[ink::test]
+        fn burn_without_burn_to_address_works() {
+            let mut erc20 = Erc20::new_with_burn_to_address(100, false);
+
+            assert_eq!(erc20.burn(30), Ok(()));
+            assert_eq!(erc20.total_supply(), 70);
+            assert_eq!(erc20.balance_of(DEAD.into()), 0);
+        }
+
+        #[ink::test]
+        fn approve_capped_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve_capped(accounts.bob, 500), Ok(100));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+
+            match recorded_event(1) {
+                Event::Approval(Approval { value, .. }) => assert_eq!(value, 100),
+                _ => panic!("Expected an Approval event"),
+            }
+
+            assert_eq!(erc20.approve_capped(accounts.charlie, 40), Ok(40));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 40);
+        }
+
+        #[ink::test]
+        fn voting_power_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(10_000);
+            assert_eq!(erc20.voting_power(accounts.alice), 100);
+            assert_eq!(erc20.voting_power(accounts.bob), 0);
+
+            assert_eq!(erc20.transfer(accounts.bob, 1), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 8), Ok(()));
+            assert_eq!(erc20.transfer(accounts.django, 99), Ok(()));
+            assert_eq!(erc20.voting_power(accounts.bob), 1);
+            assert_eq!(erc20.voting_power(accounts.charlie), 2);
+            assert_eq!(erc20.voting_power(accounts.django), 9);
+            assert_eq!(erc20.voting_power(accounts.alice), 99);
+        }
+
+        #[ink::test]
+        fn isqrt_works() {
+            assert_eq!(Erc20::isqrt(0), 0);
+            assert_eq!(Erc20::isqrt(1), 1);
+            assert_eq!(Erc20::isqrt(2), 1);
+            assert_eq!(Erc20::isqrt(3), 1);
+            assert_eq!(Erc20::isqrt(4), 2);
+            assert_eq!(Erc20::isqrt(15), 3);
+            assert_eq!(Erc20::isqrt(16), 4);
+            assert_eq!(Erc20::isqrt(u128::MAX), u128::from(u64::MAX));
+        }
+
+        #[ink::test]
+        fn mul_div_works() {
+            let max = u128::MAX;
+
+            assert_eq!(Erc20::mul_div(7, 3, 2), Some((10, 1)));
+            assert_eq!(Erc20::mul_div(max, max, max), Some((max, 0)));
+            assert_eq!(
+                Erc20::mul_div(max, 3, 4),
+                Some((255_211_775_190_703_847_597_530_955_573_826_158_591, 1))
+            );
+            assert_eq!(
+                Erc20::mul_div(max, max - 2, max - 1),
+                Some((max - 2, max - 2))
+            );
+            assert_eq!(Erc20::mul_div(max, 2, 1), None);
+            assert_eq!(Erc20::mul_div(5, 7, 0), None);
+        }
+
+        #[ink::test]
+        fn lock_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.lock(accounts.alice, 50), Ok(()));
+            assert_eq!(erc20.locked_of(accounts.alice), 50);
+
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 1), Err(Error::BalanceLocked));
+            assert_eq!(erc20.balance_of(accounts.alice), 50);
+
+            assert_eq!(erc20.unlock(accounts.alice, 20), Ok(()));
+            assert_eq!(erc20.locked_of(accounts.alice), 30);
+            assert_eq!(erc20.transfer(accounts.bob, 20), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 30);
+        }
+
+        #[ink::test]
+        fn lock_failed_with_balancelocked() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.lock(accounts.alice, 50), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 30), (accounts.charlie, 30)]),
+                Err(Error::BalanceLocked)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 51),
+                Err(Error::BalanceLocked)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn burn_failed_with_balancelocked() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_burn_to_address(100, true);
+            assert_eq!(erc20.lock(accounts.alice, 50), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(erc20.burn(51), Err(Error::BalanceLocked));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.burn_from(accounts.alice, 51),
+                Err(Error::BalanceLocked)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(DEAD.into()), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn lock_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.lock(accounts.alice, 50), Err(Error::NotIssuer));
+            assert_eq!(erc20.unlock(accounts.alice, 50), Err(Error::NotIssuer));
+            assert_eq!(erc20.locked_of(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn holders_page_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
+            assert_eq!(erc20.transfer(accounts.django, 30), Ok(()));
+            assert_eq!(erc20.transfer(accounts.eve, 5), Ok(()));
+
+            assert_eq!(
+                erc20.holders_page(0, 2),
+                vec![(accounts.alice, 35), (accounts.bob, 10)]
+            );
+            assert_eq!(
+                erc20.holders_page(2, 2),
+                vec![(accounts.charlie, 20), (accounts.django, 30)]
+            );
+            assert_eq!(erc20.holders_page(4, 2), vec![(accounts.eve, 5)]);
+            assert_eq!(erc20.account_count(), 5);
+            assert_eq!(erc20.holders_page(6, 2), vec![]);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.alice, 10), Ok(()));
+            assert_eq!(erc20.holders_page(0, 2), vec![(accounts.alice, 45)]);
+        }
+
+        #[ink::test]
+        fn transfer_bounds_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_min_transfer(10), Ok(()));
+            assert_eq!(erc20.set_max_transfer(20), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 20), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 30);
+
+            assert_eq!(erc20.set_max_transfer(0), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 80);
+        }
+
+        #[ink::test]
+        fn transfer_failed_with_belowmintransfer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_min_transfer(10), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(
+                erc20.transfer(accounts.bob, 9),
+                Err(Error::BelowMinTransfer)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 9),
+                Err(Error::BelowMinTransfer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn transfer_failed_with_abovemaxtransfer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_max_transfer(20), Ok(()));
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+
+            assert_eq!(
+                erc20.transfer(accounts.bob, 21),
+                Err(Error::AboveMaxTransfer)
+            );
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 20), (accounts.charlie, 21)]),
+                Err(Error::AboveMaxTransfer)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 21),
+                Err(Error::AboveMaxTransfer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn set_transfer_bounds_failed_with_invalidtransferbounds() {
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.set_max_transfer(20), Ok(()));
+
+            assert_eq!(
+                erc20.set_min_transfer(21),
+                Err(Error::InvalidTransferBounds)
+            );
+            assert_eq!(erc20.set_min_transfer(20), Ok(()));
+            assert_eq!(
+                erc20.set_max_transfer(19),
+                Err(Error::InvalidTransferBounds)
+            );
+            assert_eq!(erc20.min_transfer(), 20);
+            assert_eq!(erc20.max_transfer(), 20);
+        }
+
+        #[ink::test]
+        fn set_transfer_bounds_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.set_min_transfer(10), Err(Error::NotIssuer));
+            assert_eq!(erc20.set_max_transfer(20), Err(Error::NotIssuer));
+            assert_eq!(erc20.min_transfer(), 0);
+            assert_eq!(erc20.max_transfer(), 0);
+        }
+
+        #[ink::test]
+        fn transfer_from_returning_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 50), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from_returning(accounts.alice, accounts.charlie, 20),
+                Ok(30)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 30);
+            assert_eq!(
+                erc20.transfer_from_returning(accounts.alice, accounts.charlie, 30),
+                Ok(0)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(
+                erc20.transfer_from_returning(accounts.alice, accounts.charlie, 1),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 50);
+        }
+
+        #[ink::test]
+        fn rounding_mode_fee_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(10_000);
+            assert_eq!(erc20.transfer(accounts.bob, 1_000), Ok(()));
+            assert_eq!(erc20.set_fee_bps(250), Ok(()));
+            assert_eq!(erc20.rounding_mode(), RoundingMode::Down);
+
+            // 2.5% of 60 is 1.5.
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 60), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.charlie), 59);
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.set_rounding_mode(RoundingMode::Up), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.django, 60), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.django), 58);
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.set_rounding_mode(RoundingMode::Nearest), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.eve, 60), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.eve), 58);
+            // 2.5% of 59 is 1.475.
+            assert_eq!(erc20.transfer(accounts.frank, 59), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.frank), 58);
+
+            assert_eq!(erc20.balance_of(accounts.alice), 9_006);
+            assert_eq!(erc20.total_supply(), 10_000);
+        }
+
+        #[ink::test]
+        fn rounding_mode_distribute_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 250), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 150), Ok(()));
+            let balances = |erc20: &Erc20| {
+                (
+                    erc20.balance_of(accounts.alice),
+                    erc20.balance_of(accounts.bob),
+                    erc20.balance_of(accounts.charlie),
+                )
+            };
+
+            // 600 / 250 / 150 of 1000 earn 4.2 / 1.75 / 1.05, with one unit of
+            // dust for the issuer.
+            assert_eq!(erc20.distribute(7), Ok(()));
+            assert_eq!(balances(&erc20), (605, 251, 151));
+
+            // 605 / 251 / 151 of 1007 earn 4.21 / 1.74 / 1.05.
+            assert_eq!(erc20.set_rounding_mode(RoundingMode::Nearest), Ok(()));
+            assert_eq!(erc20.distribute(7), Ok(()));
+            assert_eq!(balances(&erc20), (609, 253, 152));
+
+            // 609 / 253 / 152 of 1014 earn 4.2 / 1.75 / 1.05, but rounding up
+            // runs out before the last account.
+            assert_eq!(erc20.set_rounding_mode(RoundingMode::Up), Ok(()));
+            assert_eq!(erc20.distribute(7), Ok(()));
+            assert_eq!(balances(&erc20), (614, 255, 152));
+            assert_eq!(erc20.total_supply(), 1021);
+        }
+
+        #[ink::test]
+        fn set_rounding_mode_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.set_rounding_mode(RoundingMode::Up),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.rounding_mode(), RoundingMode::Down);
+        }
+
+        #[ink::test]
+        fn approve_and_notify_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            mock_contract(accounts.charlie, || true);
+
+            assert_eq!(
+                erc20.approve_and_notify(accounts.bob, 10, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.approve_and_notify(accounts.charlie, 20, vec![7]),
+                Ok(())
+            );
+
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 20);
+            assert_eq!(
+                mock_calls(),
+                vec![(
+                    accounts.charlie,
+                    Callback::ReceiveApproval(accounts.alice, 20, vec![7])
+                )]
+            );
+        }
+
+        #[ink::test]
+        fn approve_and_notify_lets_spender_pull() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (alice, bob, charlie) = (accounts.alice, accounts.bob, accounts.charlie);
+
+            let mut erc20 = Erc20::new(100);
+
+            // Bob pulls the approved tokens to Charlie from within the
+            // notification.
+            mock_contract(bob, move || {
+                set_caller(bob);
+                let pulled = reenter(|token| token.transfer_from(alice, charlie, 10));
+                set_caller(alice);
+
+                pulled.is_ok()
+            });
+
+            assert_eq!(erc20.approve_and_notify(bob, 10, Vec::new()), Ok(()));
+
+            assert_eq!(erc20.allowance(alice, bob), 0);
+            assert_eq!(erc20.balance_of(alice), 90);
+            assert_eq!(erc20.balance_of(charlie), 10);
+            assert_eq!(erc20.holder_count(), 2);
+            assert_eq!(erc20.account_count(), 2);
+            assert_eq!(erc20.holders_page(0, 10), vec![(alice, 90), (charlie, 10)]);
+            assert_eq!(erc20.recent_transfers(10)[0].1, charlie);
+            assert!(!erc20.entered);
+        }
+
+        #[ink::test]
+        fn approve_and_notify_failed_with_reentrancy() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            // Bob's callback tries to approve Charlie from within his own
+            // notification.
+            let reentered = std::rc::Rc::new(core::cell::Cell::new(None));
+            let inner = reentered.clone();
+            let charlie = accounts.charlie;
+            mock_contract(accounts.bob, move || {
+                inner.set(Some(reenter(|token| {
+                    token.approve_and_notify(charlie, 10, Vec::new())
+                })));
+                true
+            });
+
+            assert_eq!(
+                erc20.approve_and_notify(accounts.bob, 10, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(reentered.take(), Some(Err(Error::Reentrancy)));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.charlie), 0);
+
+            assert_eq!(erc20.pause(), Ok(()));
+            assert_eq!(
+                erc20.approve_and_notify(accounts.bob, 10, Vec::new()),
+                Err(Error::Paused)
+            );
+            assert!(!erc20.entered);
+        }
+
+        #[ink::test]
+        fn claim_airdrop_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 205), Ok(()));
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.announce_airdrop(1, 1_000), Ok(()));
+            assert_eq!(erc20.airdrop_rate(1), Some(1_000));
+
+            // 10% of the 205 held at the snapshot, rounded down.
+            set_caller(accounts.bob);
+            assert_eq!(erc20.claim_airdrop(1, 1_000), Ok(20));
+            assert_eq!(erc20.balance_of(accounts.bob), 325);
+            assert_eq!(erc20.total_supply(), 1020);
+            assert!(erc20.has_claimed_airdrop(1, accounts.bob));
+            assert!(!erc20.has_claimed_airdrop(1, accounts.alice));
+        }
+
+        #[ink::test]
+        fn claim_airdrop_failed_with_alreadyclaimed() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 200), Ok(()));
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.announce_airdrop(1, 1_000), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.claim_airdrop(1, 1_000), Ok(20));
+            assert_eq!(erc20.claim_airdrop(1, 1_000), Err(Error::AlreadyClaimed));
+            assert_eq!(erc20.balance_of(accounts.bob), 220);
+            assert_eq!(erc20.total_supply(), 1020);
+        }
+
+        #[ink::test]
+        fn claim_airdrop_failed_with_airdropnotfound() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.claim_airdrop(1, 1_000), Err(Error::AirdropNotFound));
+            assert_eq!(
+                erc20.announce_airdrop(2, 1_000),
+                Err(Error::InvalidSnapshot)
+            );
+            assert_eq!(erc20.announce_airdrop(1, 1_000), Ok(()));
+            assert_eq!(erc20.claim_airdrop(1, 5_000), Err(Error::InvalidPercent));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.announce_airdrop(1, 5_000), Err(Error::NotIssuer));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn claim_airdrop_waits_for_mint_delay() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 2);
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.announce_airdrop(1, 1_000), Ok(()));
+
+            assert_eq!(erc20.claim_airdrop(1, 1_000), Ok(100));
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.pending_issue(0), Some((accounts.alice, 100, 2)));
+            assert!(erc20.has_claimed_airdrop(1, accounts.alice));
+        }
+
+        #[ink::test]
+        fn announce_airdrop_failed_with_invalidpercent() {
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.snapshot(), Ok(1));
+
+            assert_eq!(
+                erc20.announce_airdrop(1, BASIS_POINTS + 1),
+                Err(Error::InvalidPercent)
+            );
+            assert_eq!(erc20.airdrop_rate(1), None);
+        }
+
+        #[ink::test]
+        fn renounce_issuership_drops_airdrops() {
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.announce_airdrop(1, 1_000), Ok(()));
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
+
+            assert_eq!(erc20.airdrop_rate(1), None);
+            assert_eq!(erc20.claim_airdrop(1, 1_000), Err(Error::AirdropNotFound));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn to_base_units_works() {
+            let erc20 = Erc20::new_with_metadata(100, None, None, 18);
+            assert_eq!(
+                erc20.to_base_units(1_234_567_000_000_000_000, 6),
+                Ok(1_234_567)
+            );
+            assert_eq!(erc20.to_base_units(1_999_999_999_999, 6), Ok(1));
+            assert_eq!(
+                erc20.to_base_units(Balance::MAX, 0),
+                Ok(Balance::MAX / 10u128.pow(18))
+            );
+            assert_eq!(erc20.to_base_units(42, 18), Ok(42));
+
+            let erc20 = Erc20::new_with_metadata(100, None, None, 6);
+            assert_eq!(
+                erc20.to_base_units(1_234_567, 18),
+                Ok(1_234_567_000_000_000_000)
+            );
+        }
+
+        #[ink::test]
+        fn to_base_units_failed_with_overflow() {
+            let erc20 = Erc20::new_with_metadata(100, None, None, 6);
+
+            assert_eq!(
+                erc20.to_base_units(Balance::MAX / 10, 18),
+                Err(Error::Overflow)
+            );
+            assert_eq!(erc20.to_base_units(1, 255), Err(Error::Overflow));
+        }
+
+        #[ink::test]
+        fn spender_whitelist_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.approve(accounts.charlie, 50), Ok(()));
+            assert_eq!(erc20.set_spender_whitelist_enabled(true), Ok(()));
+            assert_eq!(erc20.whitelist_spender(accounts.bob), Ok(()));
+            assert!(erc20.is_whitelisted_spender(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 10),
+                Ok(())
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(erc20.unwhitelist_spender(accounts.bob), Ok(()));
+            assert_eq!(erc20.set_spender_whitelist_enabled(false), Ok(()));
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 10),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.django), 20);
+        }
+
+        #[ink::test]
+        fn transfer_from_failed_with_spendernotwhitelisted() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.charlie, 50), Ok(()));
+            assert_eq!(erc20.set_spender_whitelist_enabled(true), Ok(()));
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 10),
+                Err(Error::SpenderNotWhitelisted)
+            );
+            assert_eq!(
+                erc20.whitelist_spender(accounts.charlie),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+        }
+
+        #[ink::test]
+        fn total_minted_and_burned_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.total_minted(), 1000);
+            assert_eq!(erc20.total_burned(), 0);
+
+            assert_eq!(erc20.issue(500), Ok(()));
+            assert_eq!(erc20.burn(200), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn(50), Ok(()));
+
+            assert_eq!(erc20.total_minted(), 1500);
+            assert_eq!(erc20.total_burned(), 250);
+            assert_eq!(erc20.total_supply(), 1250);
+            assert_eq!(
+                erc20.total_minted() - erc20.total_burned(),
+                erc20.total_supply()
+            );
+        }
+
+        #[ink::test]
+        fn transfer_all_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer_all(accounts.charlie), Ok(30));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 30);
+
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.transfer_all(accounts.charlie), Ok(0));
+            assert_eq!(ink_env::test::recorded_events().count(), events_before);
+            assert_eq!(erc20.balance_of(accounts.charlie), 30);
+        }
+
+        #[ink::test]
+        fn transfer_detailed_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 20), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            assert_eq!(erc20.transfer(accounts.charlie, 10), Ok(()));
+            assert!(matches!(recorded_event(2), Event::Transfer(_)));
+            match recorded_event(3) {
+                Event::TransferDetailed(TransferDetailed {
+                    from,
+                    to,
+                    value,
+                    block,
+                    caller,
+                }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(to, accounts.charlie);
+                    assert_eq!(value, 10);
+                    assert!(block > 0);
+                    assert_eq!(caller, accounts.alice);
+                }
+                _ => panic!("Expected a TransferDetailed event"),
+            }
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 20),
+                Ok(())
+            );
+            assert!(matches!(recorded_event(4), Event::Transfer(_)));
+            assert!(matches!(recorded_event(5), Event::TransferFrom(_)));
+            match recorded_event(6) {
+                Event::TransferDetailed(TransferDetailed {
+                    from,
+                    value,
+                    caller,
+                    ..
+                }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(value, 20);
+                    assert_eq!(caller, accounts.bob);
+                }
+                _ => panic!("Expected a TransferDetailed event"),
+            }
+        }
+
+        #[ink::test]
+        fn transfer_detailed_works_with_fee() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 200), Ok(()));
+            assert_eq!(erc20.set_fee_bps(1_000), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_with_memo(accounts.charlie, 100, b"deposit-42".to_vec()),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 90);
+            match recorded_event(5) {
+                Event::TransferDetailed(TransferDetailed { value, .. }) => {
+                    assert_eq!(value, 90);
+                }
+                _ => panic!("Expected a TransferDetailed event"),
+            }
+            match recorded_event(6) {
+                Event::TransferMemo(TransferMemo { value, .. }) => {
+                    assert_eq!(value, 90);
+                }
+                _ => panic!("Expected a TransferMemo event"),
+            }
+        }
+
+        #[ink::test]
+        fn balance_of_u64_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new(Balance::from(u64::MAX));
+            assert_eq!(erc20.balance_of_u64(accounts.alice), Ok(u64::MAX));
+            assert_eq!(erc20.balance_of_u64(accounts.bob), Ok(0));
+        }
+
+        #[ink::test]
+        fn balance_of_u64_failed_with_overflow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let erc20 = Erc20::new(Balance::from(u64::MAX) + 1);
+            assert_eq!(erc20.balance_of_u64(accounts.alice), Err(Error::Overflow));
+        }
+
+        #[ink::test]
+        fn auto_burn_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_auto_burn(100, accounts.frank);
+            assert_eq!(erc20.dead_address(), accounts.frank);
+
+            assert_eq!(erc20.transfer(accounts.frank, 30), Ok(()));
+            assert_eq!(erc20.total_supply(), 70);
+            assert_eq!(erc20.balance_of(accounts.frank), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            match recorded_event(1) {
+                Event::Burn(Burn { from, value }) => {
+                    assert_eq!(from, accounts.alice);
+                    assert_eq!(value, 30);
+                }
+                _ => panic!("Expected a Burn event"),
+            }
+
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.total_supply(), 70);
+        }
+
+        #[ink::test]
+        fn auto_burn_works_with_transfer_from() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_auto_burn(100, accounts.frank);
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.frank, 10),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.frank), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+            assert_eq!(erc20.total_supply(), 90);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn auto_burn_works_with_transfer_batch() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new_with_auto_burn(100, accounts.frank);
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.frank, 10), (accounts.bob, 20)]),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.frank), 0);
+            assert_eq!(erc20.balance_of(accounts.bob), 20);
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            assert_eq!(erc20.total_supply(), 90);
+        }
+
+        #[ink::test]
+        fn transfer_to_dead_without_auto_burn_works() {
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.dead_address(), DEAD.into());
+
+            assert_eq!(erc20.transfer(DEAD.into(), 30), Ok(()));
+            assert_eq!(erc20.total_supply(), 100);
+            assert_eq!(erc20.balance_of(DEAD.into()), 30);
+        }
+
+        #[ink::test]
+        fn top_holders_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 30), Ok(()));
+            assert_eq!(erc20.transfer(accounts.django, 20), Ok(()));
+            assert_eq!(erc20.transfer(accounts.eve, 15), Ok(()));
+
+            assert_eq!(
+                erc20.top_holders(3),
+                vec![
+                    (accounts.charlie, 30),
+                    (accounts.alice, 25),
+                    (accounts.django, 20)
+                ]
+            );
+            assert_eq!(erc20.top_holders(10).len(), 5);
+            assert_eq!(erc20.top_holders(0), vec![]);
+        }
+
+        #[ink::test]
+        fn approve_to_recipient_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.approve_to_recipient(accounts.bob, accounts.charlie, 50),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 50);
+            assert_eq!(
+                erc20.allowance_recipient(accounts.alice, accounts.bob),
+                Some(accounts.charlie)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 20),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 30),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 50);
+            assert_eq!(
+                erc20.allowance_recipient(accounts.alice, accounts.bob),
+                None
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                erc20.approve_to_recipient(accounts.bob, accounts.charlie, 10),
+                Ok(())
+            );
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(
+                erc20.allowance_recipient(accounts.alice, accounts.bob),
+                None
+            );
+        }
+
+        #[ink::test]
+        fn transfer_from_failed_with_recipientmismatch() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.approve_to_recipient(accounts.bob, accounts.charlie, 50),
+                Ok(())
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 20),
+                Err(Error::RecipientMismatch)
+            );
+            assert_eq!(
+                erc20.burn_from(accounts.alice, 20),
+                Err(Error::RecipientMismatch)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn reconcile_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 40), Ok(()));
+
+            assert_eq!(erc20.reconcile(accounts.bob, 70), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 70);
+            assert_eq!(erc20.total_supply(), 130);
+            match recorded_event(4) {
+                Event::Adjustment(Adjustment { owner, old, new }) => {
+                    assert_eq!(owner, accounts.bob);
+                    assert_eq!(old, 40);
+                    assert_eq!(new, 70);
+                }
+                _ => panic!("Expected an Adjustment event"),
+            }
+
+            assert_eq!(erc20.reconcile(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.total_supply(), 70);
+            assert_eq!(erc20.sum_balances(), Ok(70));
+        }
+
+        #[ink::test]
+        fn reconcile_keeps_escrow() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(200);
+            assert_eq!(erc20.create_vesting(accounts.bob, 100, 10), Ok(()));
+
+            assert_eq!(
+                erc20.reconcile(accounts.eve, 99),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.balance_of(accounts.eve), 100);
+            assert_eq!(erc20.total_supply(), 200);
+
+            assert_eq!(erc20.reconcile(accounts.eve, 100), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.eve), 100);
+        }
+
+        #[ink::test]
+        fn reconcile_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.reconcile(accounts.bob, 70), Err(Error::NotIssuer));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn withdraw_stream_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.create_stream(accounts.bob, 10, 5), Ok(0));
+            assert_eq!(erc20.balance_of(accounts.alice), 50);
+            assert_eq!(erc20.balance_of(accounts.eve), 50);
+
+            for _ in 0..2 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            set_caller(accounts.bob);
+            assert_eq!(erc20.withdraw_stream(0), Ok(20));
+            assert_eq!(erc20.withdraw_stream(0), Ok(0));
+            assert_eq!(erc20.stream(0).map(|stream| stream.withdrawn), Some(20));
+
+            for _ in 0..10 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(erc20.withdraw_stream(0), Ok(30));
+            assert_eq!(erc20.balance_of(accounts.bob), 50);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            assert_eq!(erc20.stream(0), None);
+            assert_eq!(erc20.withdraw_stream(0), Err(Error::StreamNotFound));
+        }
+
+        #[ink::test]
+        fn cancel_stream_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.create_stream(accounts.bob, 10, 5), Ok(0));
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            set_caller(accounts.bob);
+            assert_eq!(erc20.withdraw_stream(0), Ok(10));
+
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            set_caller(accounts.alice);
+            assert_eq!(erc20.cancel_stream(0), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 30);
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            assert_eq!(erc20.stream(0), None);
+        }
+
+        #[ink::test]
+        fn stream_failed_with_notstreamparty() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.create_stream(accounts.bob, 30, 5),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.create_stream(accounts.bob, 10, 5), Ok(0));
+
+            set_caller(accounts.charlie);
+            assert_eq!(erc20.withdraw_stream(0), Err(Error::NotStreamParty));
+            assert_eq!(erc20.cancel_stream(0), Err(Error::NotStreamParty));
+            set_caller(accounts.alice);
+            assert_eq!(erc20.withdraw_stream(0), Err(Error::NotStreamParty));
+            assert_eq!(erc20.balance_of(accounts.eve), 50);
+        }
+
+        #[ink::test]
+        fn create_stream_runs_transfer_checks() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.eve);
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.create_stream(AccountId::from([0x0; 32]), 10, 5),
+                Err(Error::ZeroAddress)
+            );
+
+            assert_eq!(erc20.lock(accounts.alice, 60), Ok(()));
+            assert_eq!(
+                erc20.create_stream(accounts.bob, 10, 5),
+                Err(Error::BalanceLocked)
+            );
+
+            assert_eq!(erc20.unlock(accounts.alice, 60), Ok(()));
+            assert_eq!(erc20.set_allowlist_enabled(true), Ok(()));
+            assert_eq!(
+                erc20.create_stream(accounts.bob, 10, 5),
+                Err(Error::NotAllowlisted)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            assert_eq!(erc20.stream(0), None);
+        }
+
+        #[ink::test]
+        fn status_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.status(accounts.bob), (false, false));
+
+            assert_eq!(erc20.freeze(accounts.bob), Ok(()));
+            assert_eq!(erc20.status(accounts.bob), (false, true));
+            assert_eq!(erc20.status(accounts.charlie), (false, false));
+
+            assert_eq!(erc20.pause(), Ok(()));
+            assert_eq!(erc20.status(accounts.bob), (true, true));
+            assert_eq!(erc20.status(accounts.charlie), (true, false));
         }
 
-        #[ink(message)]
-        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
-            let who = Self::env().caller();
+        #[ink::test]
+        fn create_contract_with_max_decimals_works() {
+            let erc20 = Erc20::new_with_metadata(100, None, None, 18);
+            assert_eq!(erc20.token_decimals(), 18);
 
-            self.approve_help(who, spender, value)
+            let erc20 = Erc20::new_with_metadata(100, None, None, MAX_DECIMALS);
+            assert_eq!(erc20.token_decimals(), MAX_DECIMALS);
         }
 
-        #[ink(message)]
-        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
-            *self.allowances.get(&(owner, spender)).unwrap_or(&0)
+        #[ink::test]
+        #[should_panic(expected = "decimals exceed MAX_DECIMALS")]
+        fn create_contract_with_too_many_decimals_fails() {
+            Erc20::new_with_metadata(100, None, None, MAX_DECIMALS + 1);
         }
 
-        #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
-            let who = Self::env().caller();
+        #[ink::test]
+        fn merge_accounts_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            self.transfer_help(who, to, value)
-        }
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 20), Ok(()));
 
-        #[ink(message)]
-        pub fn transfer_from(
-            &mut self,
-            from: AccountId,
-            to: AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let who = Self::env().caller();
+            assert_eq!(erc20.merge_accounts(accounts.bob, accounts.charlie), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 50);
 
-            self.transfer_from_help(who, from, to, value)
+            assert_eq!(
+                erc20.merge_accounts(accounts.charlie, accounts.charlie),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.charlie), 50);
+            assert_eq!(erc20.total_supply(), 100);
         }
 
-        #[ink(message)]
-        pub fn burn(&mut self, value: Balance) -> Result<()> {
-            let who = Self::env().caller();
+        #[ink::test]
+        fn merge_accounts_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            self.burn_help(who, value)
+            let mut erc20 = Erc20::new(100);
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.merge_accounts(accounts.alice, accounts.bob),
+                Err(Error::NotIssuer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
         }
 
-        #[ink(message)]
-        pub fn issue(&mut self, value: Balance) -> Result<()> {
-            let who = Self::env().caller();
+        #[ink::test]
+        fn merge_accounts_failed_with_zeroaddress() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            self.issue_help(who, value)
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+
+            assert_eq!(
+                erc20.merge_accounts(accounts.bob, AccountId::from([0; 32])),
+                Err(Error::ZeroAddress)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 30);
         }
 
-        pub fn transfer_help(
-            &mut self,
-            from: AccountId,
-            to: AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let from_balance = self.balance_of(from);
+        #[ink::test]
+        fn transfer_with_sig_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (secret, owner) = keypair(1);
 
-            if from_balance < value {
-                return Err(Error::InsufficentBalance);
-            }
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(owner, 500), Ok(()));
 
-            self.balances.insert(from, from_balance - value);
-            let to_balance = self.balance_of(to);
-            self.balances.insert(to, to_balance + value);
+            let signature = sign(
+                &secret,
+                erc20.transfer_with_sig_hash(owner, accounts.bob, 100, 0),
+            );
 
-            Self::env().emit_event(Transfer {
-                from: from,
-                to: to,
-                value: value,
-            });
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_with_sig(owner, accounts.bob, 100, 0, signature),
+                Ok(())
+            );
 
-            Ok(())
+            assert_eq!(erc20.balance_of(owner), 400);
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.nonce_of(owner), 1);
         }
 
-        pub fn approve_help(
-            &mut self,
-            owner: AccountId,
-            spender: AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let owner_balance = self.balance_of(owner);
+        #[ink::test]
+        fn transfer_with_sig_failed_with_badnonce() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (secret, owner) = keypair(1);
 
-            if owner_balance < value {
-                return Err(Error::InsufficentBalance);
-            }
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(owner, 500), Ok(()));
 
-            self.balances.insert(owner, owner_balance - value);
-            let allowance = self.allowance(owner, spender);
-            self.allowances.insert((owner, spender), allowance + value);
+            let signature = sign(
+                &secret,
+                erc20.transfer_with_sig_hash(owner, accounts.bob, 100, 0),
+            );
 
-            Self::env().emit_event(Approval {
-                owner: owner,
-                spender: spender,
-                value: value,
-            });
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_with_sig(owner, accounts.bob, 100, 0, signature),
+                Ok(())
+            );
+            assert_eq!(
+                erc20.transfer_with_sig(owner, accounts.bob, 100, 0, signature),
+                Err(Error::BadNonce)
+            );
 
-            Ok(())
+            assert_eq!(erc20.balance_of(owner), 400);
+            assert_eq!(erc20.nonce_of(owner), 1);
         }
 
-        pub fn transfer_from_help(
-            &mut self,
-            from: AccountId,
-            owner: AccountId,
-            to: AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let allowance = self.allowance(owner, from);
+        #[ink::test]
+        fn transfer_with_sig_failed_with_invalidsignature() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let (secret, owner) = keypair(1);
 
-            if allowance < value {
-                return Err(Error::InsufficentAllowance);
-            }
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(owner, 500), Ok(()));
 
-            self.allowances.insert((owner, from), allowance - value);
-            let to_balance = self.balance_of(to);
-            self.balances.insert(to, to_balance + value);
+            let signature = sign(
+                &secret,
+                erc20.transfer_with_sig_hash(owner, accounts.bob, 100, 0),
+            );
 
-            Self::env().emit_event(TransferFrom {
-                from: from,
-                owner: owner,
-                to: to,
-                value: value,
-            });
+            set_caller(accounts.charlie);
+            assert_eq!(
+                erc20.transfer_with_sig(owner, accounts.bob, 500, 0, signature),
+                Err(Error::InvalidSignature)
+            );
 
-            Ok(())
+            assert_eq!(erc20.balance_of(owner), 500);
+            assert_eq!(erc20.nonce_of(owner), 0);
         }
 
-        pub fn burn_help(&mut self, from: AccountId, value: Balance) -> Result<()> {
-            let from_balance = self.balance_of(from);
-
-            if from_balance < value {
-                return Err(Error::InsufficentBalance);
-            }
+        #[ink::test]
+        fn burn_sweeps_dust_to_issuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            self.balances.insert(from, from_balance - value);
-            self.total_supply = self.total_supply() - value;
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.set_dust_threshold(10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
 
-            Self::env().emit_event(Burn {
-                from: from,
-                value: value,
-            });
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn(95), Ok(()));
 
-            Ok(())
-        }
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 905);
+            assert_eq!(erc20.total_supply(), 905);
 
-        pub fn issue_help(&mut self, from: AccountId, value: Balance) -> Result<()> {
-            if from != self.issuer {
-                return Err(Error::NotIssuer);
+            match recorded_event(ink_env::test::recorded_events().count() - 1) {
+                Event::Transfer(Transfer { from, to, value }) => {
+                    assert_eq!(from, accounts.bob);
+                    assert_eq!(to, accounts.alice);
+                    assert_eq!(value, 5);
+                }
+                _ => panic!("Expected a Transfer event"),
             }
+        }
 
-            let from_balance = self.balance_of(from);
+        #[ink::test]
+        fn burn_keeps_locked_dust() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            self.balances.insert(from, from_balance + value);
-            self.total_supply = self.total_supply() + value;
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.set_dust_threshold(10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.lock(accounts.bob, 3), Ok(()));
 
-            Self::env().emit_event(Issue {
-                issuer: from,
-                value: value,
-            });
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn(95), Ok(()));
 
-            Ok(())
+            assert_eq!(erc20.balance_of(accounts.bob), 3);
+            assert_eq!(erc20.balance_of(accounts.alice), 902);
         }
-    }
-
-    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
-    /// module and test functions are marked with a `#[test]` attribute.
-    /// The below code is technically just normal Rust code.
-    #[cfg(test)]
-    mod tests {
-        /// Imports all the definitions from the outer scope so we can use them here.
-        use super::*;
-        use ink_lang as ink;
 
         #[ink::test]
-        fn create_contract_works() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn burn_keeps_dust_after_renounce() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            let erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.set_dust_threshold(10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
-            assert_eq!(erc20.balance_of(accounts.bob), 0);
-            assert_eq!(ink_env::test::recorded_events().count(), 1);
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn(95), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.bob), 5);
+            assert_eq!(erc20.balance_of(Erc20::zero_account()), 0);
         }
 
         #[ink::test]
-        fn transfer_works() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn burn_keeps_remainder_at_dust_threshold() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
             let mut erc20 = Erc20::new(1000);
-
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
-
+            assert_eq!(erc20.set_dust_threshold(10), Ok(()));
             assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
 
-            assert_eq!(erc20.balance_of(accounts.alice), 900);
-            assert_eq!(erc20.balance_of(accounts.bob), 100);
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            set_caller(accounts.bob);
+            assert_eq!(erc20.burn(90), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+
+            assert_eq!(erc20.set_dust_threshold(20), Err(Error::NotIssuer));
+            assert_eq!(erc20.dust_threshold(), 10);
         }
 
         #[ink::test]
-        fn transfer_failed_with_insufficentbalance() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn mint_inflation_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
             let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 500), Ok(()));
+            assert_eq!(erc20.set_inflation(200, 2), Ok(()));
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
 
-            assert_eq!(
-                erc20.transfer(accounts.bob, 2000),
-                Err(Error::InsufficentBalance)
-            );
+            set_caller(accounts.bob);
+            assert_eq!(erc20.mint_inflation(), Ok(20));
+
+            assert_eq!(erc20.total_supply(), 1020);
+            assert_eq!(erc20.balance_of(accounts.alice), 520);
+            assert_eq!(erc20.balance_of(accounts.bob), 500);
+            assert_eq!(erc20.last_inflation_block(), 2);
         }
 
         #[ink::test]
-        fn approve_works() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
-
+        fn mint_inflation_failed_with_timelocknotelapsed() {
             let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.mint_inflation(), Err(Error::TimelockNotElapsed));
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
-            assert_eq!(erc20.balance_of(accounts.bob), 0);
-            assert_eq!(erc20.balance_of(accounts.charlie), 0);
-            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(erc20.set_inflation(200, 2), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(erc20.mint_inflation(), Err(Error::TimelockNotElapsed));
 
-            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(erc20.mint_inflation(), Ok(20));
+            assert_eq!(erc20.mint_inflation(), Err(Error::TimelockNotElapsed));
 
-            assert_eq!(erc20.balance_of(accounts.alice), 900);
-            assert_eq!(erc20.balance_of(accounts.bob), 0);
-            assert_eq!(erc20.balance_of(accounts.charlie), 0);
-            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            assert_eq!(erc20.total_supply(), 1020);
+        }
 
-            let callee =
-                ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap_or([0x0; 32].into());
-            let mut data = ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4]));
-            data.push_arg(&accounts.bob);
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
-            );
+        #[ink::test]
+        fn mint_inflation_waits_for_mint_delay() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.charlie, 50),
-                Ok(())
-            );
+            let mut erc20 = Erc20::new_with_mint_delay(1000, 2);
+            assert_eq!(erc20.set_inflation(200, 1), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
 
-            assert_eq!(erc20.balance_of(accounts.alice), 900);
-            assert_eq!(erc20.balance_of(accounts.bob), 0);
-            assert_eq!(erc20.balance_of(accounts.charlie), 50);
-            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 50);
+            assert_eq!(erc20.mint_inflation(), Ok(20));
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.pending_issue(0), Some((accounts.alice, 20, 3)));
         }
 
         #[ink::test]
-        fn approve_failed_with_insufficientallowance() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn mint_inflation_skips_empty_periods() {
+            let mut erc20 = Erc20::new(10);
+            assert_eq!(erc20.set_inflation(200, 1), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+
+            let events_before = ink_env::test::recorded_events().count();
+            assert_eq!(erc20.mint_inflation(), Ok(0));
+            assert_eq!(ink_env::test::recorded_events().count(), events_before);
+            assert_eq!(erc20.last_inflation_block(), 1);
+            assert_eq!(erc20.total_supply(), 10);
+        }
 
+        #[ink::test]
+        fn renounce_issuership_stops_inflation() {
             let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.set_inflation(200, 1), Ok(()));
+            assert_eq!(erc20.renounce_issuership(), Ok(()));
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
 
+            assert_eq!(erc20.mint_inflation(), Err(Error::TimelockNotElapsed));
             assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
-            assert_eq!(erc20.balance_of(accounts.bob), 0);
-            assert_eq!(erc20.balance_of(accounts.charlie), 0);
-            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
-
-            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.balance_of(Erc20::zero_account()), 0);
+        }
 
-            assert_eq!(erc20.balance_of(accounts.alice), 900);
-            assert_eq!(erc20.balance_of(accounts.bob), 0);
-            assert_eq!(erc20.balance_of(accounts.charlie), 0);
-            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        #[ink::test]
+        fn get_config_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            let callee =
-                ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap_or([0x0; 32].into());
-            let mut data = ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4]));
-            data.push_arg(&accounts.bob);
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
+            let mut erc20 = Erc20::new_with_metadata(
+                1000,
+                Some(String::from("Token")),
+                Some(String::from("TKN")),
+                12,
             );
+            assert_eq!(erc20.set_fee_bps(50), Ok(()));
+            assert_eq!(erc20.set_max_transfer(500), Ok(()));
 
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.charlie, 500),
-                Err(Error::InsufficentAllowance)
+                erc20.get_config(),
+                Config {
+                    issuer: accounts.alice,
+                    name: Some(String::from("Token")),
+                    symbol: Some(String::from("TKN")),
+                    decimals: 12,
+                    cap: None,
+                    paused: false,
+                    threshold: 1,
+                    mint_delay: 0,
+                    treasury: accounts.alice,
+                    unit_size: 0,
+                    strict_approve: false,
+                    cooldown_blocks: 0,
+                    clawback_window: 0,
+                    min_balance: 0,
+                    events_enabled: true,
+                    fee_bps: 50,
+                    recent_transfers_cap: DEFAULT_RECENT_TRANSFERS_CAP,
+                    supply_log_cap: DEFAULT_SUPPLY_LOG_CAP,
+                    allowlist_enabled: false,
+                    burn_to_address: false,
+                    dead: DEAD.into(),
+                    auto_burn: false,
+                    min_transfer: 0,
+                    max_transfer: 500,
+                    rounding_mode: RoundingMode::Down,
+                    spender_whitelist_enabled: false,
+                    dust_threshold: 0,
+                    inflation_bps_per_period: 0,
+                    period_blocks: 0,
+                }
             );
         }
 
         #[ink::test]
-        fn burn_works() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn transfer_failed_with_zeroaddress() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let zero = AccountId::from([0x0; 32]);
 
-            let mut erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.approve(accounts.bob, 100), Ok(()));
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.transfer(zero, 10), Err(Error::ZeroAddress));
+            assert_eq!(
+                erc20.transfer_batch(vec![(accounts.bob, 10), (zero, 10)]),
+                Err(Error::ZeroAddress)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, zero, 10),
+                Err(Error::ZeroAddress)
+            );
 
-            assert_eq!(erc20.burn(100), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(zero), 0);
 
-            assert_eq!(erc20.balance_of(accounts.alice), 900);
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            set_caller(accounts.alice);
+            assert_eq!(erc20.burn(10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+            assert_eq!(erc20.total_supply(), 90);
         }
 
         #[ink::test]
-        fn burn_failed_with_insufficientbalance() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn delegate_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
             let mut erc20 = Erc20::new(1000);
+            assert_eq!(erc20.transfer(accounts.bob, 100), Ok(()));
+            assert_eq!(erc20.transfer(accounts.charlie, 200), Ok(()));
+            assert_eq!(erc20.transfer(accounts.django, 300), Ok(()));
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            set_caller(accounts.bob);
+            assert_eq!(erc20.delegate(accounts.django), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(erc20.delegate(accounts.django), Ok(()));
+
+            assert_eq!(erc20.delegate_of(accounts.bob), Some(accounts.django));
+            assert_eq!(erc20.delegated_power(accounts.django), 300);
+            assert_eq!(erc20.balance_of(accounts.django), 300);
 
-            assert_eq!(erc20.burn(2000), Err(Error::InsufficentBalance));
+            set_caller(accounts.django);
+            assert_eq!(erc20.delegate(accounts.django), Ok(()));
+            assert_eq!(erc20.delegated_power(accounts.django), 600);
+
+            match recorded_event(ink_env::test::recorded_events().count() - 1) {
+                Event::DelegateChanged(DelegateChanged {
+                    delegator,
+                    previous,
+                    new,
+                }) => {
+                    assert_eq!(delegator, accounts.django);
+                    assert_eq!(previous, None);
+                    assert_eq!(new, accounts.django);
+                }
+                _ => panic!("Expected a DelegateChanged event"),
+            }
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.delegate(accounts.bob), Ok(()));
+            assert_eq!(erc20.delegated_power(accounts.django), 500);
+            assert_eq!(erc20.delegated_power(accounts.bob), 100);
         }
 
         #[ink::test]
-        fn issue_works() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn transfer_fee_exempt_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            let mut erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(10_000);
+            assert_eq!(erc20.transfer(accounts.bob, 2_000), Ok(()));
+            assert_eq!(erc20.set_fee_bps(250), Ok(()));
+            assert_eq!(erc20.set_fee_exempt(accounts.charlie, true), Ok(()));
+            assert!(erc20.is_fee_exempt(accounts.charlie));
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 1_000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.charlie), 1_000);
 
-            assert_eq!(erc20.issue(1000), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(erc20.transfer(accounts.bob, 400), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 1_400);
+            assert_eq!(erc20.balance_of(accounts.alice), 8_000);
 
-            assert_eq!(erc20.total_supply(), 2000);
-            assert_eq!(erc20.balance_of(accounts.alice), 2000);
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            set_caller(accounts.alice);
+            assert_eq!(erc20.set_fee_exempt(accounts.charlie, false), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(erc20.transfer(accounts.charlie, 1_000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.charlie), 1_575);
+            assert_eq!(erc20.balance_of(accounts.alice), 8_025);
         }
 
         #[ink::test]
-        fn issue_failed_with_notissuer() {
-            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
-                .expect("Cannot get accounts");
+        fn set_fee_exempt_failed_with_notissuer() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            let mut erc20 = Erc20::new(1000);
+            let mut erc20 = Erc20::new(100);
 
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.set_fee_exempt(accounts.bob, true),
+                Err(Error::NotIssuer)
+            );
+            assert!(!erc20.is_fee_exempt(accounts.bob));
+        }
+
+        #[ink::test]
+        fn safe_transfer_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+            mock_contract(accounts.charlie, || true);
+
+            assert_eq!(erc20.safe_transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.safe_transfer(accounts.charlie, 20), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 20);
+            assert_eq!(
+                mock_calls(),
+                vec![(
+                    accounts.charlie,
+                    Callback::OnErc20Received(accounts.alice, 20)
+                )]
+            );
+        }
+
+        #[ink::test]
+        fn safe_transfer_failed_with_receiverrejected() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
 
-            let callee =
-                ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap_or([0x0; 32].into());
-            let mut data = ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4]));
-            data.push_arg(&accounts.bob);
-            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
-                accounts.bob,
-                callee,
-                1000000,
-                1000000,
-                data,
+            let mut erc20 = Erc20::new(100);
+            mock_contract(accounts.charlie, || false);
+
+            assert_eq!(
+                erc20.safe_transfer(accounts.charlie, 20),
+                Err(Error::ReceiverRejected)
             );
 
-            assert_eq!(erc20.issue(1000), Err(Error::NotIssuer));
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert!(!erc20.entered);
+        }
+
+        #[ink::test]
+        fn safe_transfer_failed_with_insufficientbalance() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+
+            let mut erc20 = Erc20::new(100);
+
+            assert_eq!(
+                erc20.safe_transfer(accounts.bob, 101),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
         }
     }
 }